@@ -1,9 +1,31 @@
-#![feature(repr_simd, platform_intrinsics, test)]
+#![cfg_attr(
+    not(any(miri, feature = "portable-simd", feature = "scalar-fallback")),
+    feature(repr_simd, platform_intrinsics),
+)]
+#![cfg_attr(
+    all(feature = "portable-simd", not(any(miri, feature = "scalar-fallback"))),
+    feature(portable_simd),
+)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(test, feature(test))]
 #![allow(non_camel_case_types)]
 
-use std::fmt::Debug;
-use std::mem::{MaybeUninit, transmute};
-use std::ops::{Add, AddAssign};
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::error::Error;
+use core::fmt::{Debug, Display, LowerHex, UpperHex};
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, Sum};
+use core::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Mul, MulAssign,
+    Neg, Not, Sub, SubAssign,
+};
 
 //#################################################################################################
 //
@@ -13,313 +35,6208 @@ use std::ops::{Add, AddAssign};
 
 /*
  * A struct holding 8 u64s, for a total of 512 bits. The repr(simd) allows it to be held in
- * the special simd registers of your cpu, if there exists.
+ * the special simd registers of your cpu, if there exists. Each row of a u8x512 is a u64x8,
+ * which is why it is public: it lets the rows be handled directly, with its bitwise operators,
+ * and bitsliced types other than the ones of the library be built on it.
  */
+#[cfg(not(any(miri, feature = "portable-simd", feature = "scalar-fallback")))]
 #[repr(simd)]
 #[derive(Clone, Copy)]
-struct u64x8(u64, u64, u64, u64, u64, u64, u64, u64);
+pub struct u64x8(u64, u64, u64, u64, u64, u64, u64, u64);
+
+/*
+ * With the portable-simd feature, a u64x8 wraps the u64x8 of core::simd instead, which needs
+ * neither repr(simd) nor the platform intrinsics.
+ */
+#[cfg(all(feature = "portable-simd", not(any(miri, feature = "scalar-fallback"))))]
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct u64x8(core::simd::u64x8);
+
+/*
+ * With the scalar-fallback feature, or under miri, a u64x8 is a plain array of u64s, for the
+ * targets without simd. The scalar fallback takes precedence over the portable-simd feature.
+ */
+#[cfg(any(miri, feature = "scalar-fallback"))]
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct u64x8([u64; 8]);
 
 impl u64x8 {
     /*
      * The zero of a u64x8 (8 zeros, really).
      */
-    const ZERO: u64x8 = u64x8(0, 0, 0, 0, 0, 0, 0, 0);
+    const ZERO: u64x8 = u64x8::from_array([0; 8]);
+
+    /*
+     * A u64x8 with all of its 512 bits set.
+     */
+    const ONES: u64x8 = u64x8::from_array([!0; 8]);
+
+    /*
+     * A u64x8 with only the bit of the given lane set, with the same layout as a row of a
+     * u8x512.
+     */
+    fn lane_bit(index: usize) -> u64x8 {
+        let mut words = [0; 8];
+        words[index / 64] = 1 << (index % 64);
+        u64x8::from_array(words)
+    }
+
+    /*
+     * Creates a u64x8 from an array of 8 u64s, the u64 i holding the bits 64i to 64i+63.
+     */
+    #[cfg(not(any(miri, feature = "portable-simd", feature = "scalar-fallback")))]
+    pub const fn from_array(words: [u64; 8]) -> u64x8 {
+        u64x8(words[0], words[1], words[2], words[3], words[4], words[5], words[6], words[7])
+    }
+
+    #[cfg(all(feature = "portable-simd", not(any(miri, feature = "scalar-fallback"))))]
+    pub const fn from_array(words: [u64; 8]) -> u64x8 {
+        u64x8(core::simd::u64x8::from_array(words))
+    }
+
+    #[cfg(any(miri, feature = "scalar-fallback"))]
+    pub const fn from_array(words: [u64; 8]) -> u64x8 {
+        u64x8(words)
+    }
+
+    /*
+     * Converts a u64x8 to an array of 8 u64s, so that they can be indexed.
+     */
+    #[cfg(not(any(miri, feature = "portable-simd", feature = "scalar-fallback")))]
+    pub fn to_array(self) -> [u64; 8] {
+        [self.0, self.1, self.2, self.3, self.4, self.5, self.6, self.7]
+    }
+
+    #[cfg(all(feature = "portable-simd", not(any(miri, feature = "scalar-fallback"))))]
+    pub fn to_array(self) -> [u64; 8] {
+        self.0.to_array()
+    }
+
+    #[cfg(any(miri, feature = "scalar-fallback"))]
+    pub fn to_array(self) -> [u64; 8] {
+        self.0
+    }
+
+    /*
+     * Splits a u64x8 into the u64x4 of its 4 low u64s and the one of its 4 high u64s. The
+     * whole row is reinterpreted at once, the lanes of a simd type being laid out in order.
+     */
+    #[cfg(not(any(miri, feature = "portable-simd", feature = "scalar-fallback")))]
+    fn split(self) -> [u64x4; 2] {
+        unsafe { core::mem::transmute::<u64x8, [u64x4; 2]>(self) }
+    }
+
+    #[cfg(all(feature = "portable-simd", not(any(miri, feature = "scalar-fallback"))))]
+    fn split(self) -> [u64x4; 2] {
+        let [lo, hi] = unsafe {
+            core::mem::transmute::<core::simd::u64x8, [core::simd::u64x4; 2]>(self.0)
+        };
+
+        [u64x4(lo), u64x4(hi)]
+    }
+
+    #[cfg(any(miri, feature = "scalar-fallback"))]
+    fn split(self) -> [u64x4; 2] {
+        let [lo, hi] = unsafe { core::mem::transmute::<[u64; 8], [[u64; 4]; 2]>(self.0) };
+
+        [u64x4(lo), u64x4(hi)]
+    }
+
+    /*
+     * The inverse of split: the u64x8 whose 4 low u64s are those of lo and whose 4 high u64s
+     * are those of hi.
+     */
+    #[cfg(not(any(miri, feature = "portable-simd", feature = "scalar-fallback")))]
+    fn join(lo: u64x4, hi: u64x4) -> u64x8 {
+        unsafe { core::mem::transmute::<[u64x4; 2], u64x8>([lo, hi]) }
+    }
+
+    #[cfg(all(feature = "portable-simd", not(any(miri, feature = "scalar-fallback"))))]
+    fn join(lo: u64x4, hi: u64x4) -> u64x8 {
+        u64x8(unsafe {
+            core::mem::transmute::<[core::simd::u64x4; 2], core::simd::u64x8>([lo.0, hi.0])
+        })
+    }
+
+    #[cfg(any(miri, feature = "scalar-fallback"))]
+    fn join(lo: u64x4, hi: u64x4) -> u64x8 {
+        u64x8(unsafe { core::mem::transmute::<[[u64; 4]; 2], [u64; 8]>([lo.0, hi.0]) })
+    }
+
+    /*
+     * Reverses the order of the 512 bits: the u64s are swapped end for end, and the bits of
+     * each of them are reversed.
+     */
+    fn reverse_bits(&self) -> u64x8 {
+        let w = self.to_array();
+
+        u64x8::from_array([
+            w[7].reverse_bits(), w[6].reverse_bits(), w[5].reverse_bits(), w[4].reverse_bits(),
+            w[3].reverse_bits(), w[2].reverse_bits(), w[1].reverse_bits(), w[0].reverse_bits(),
+        ])
+    }
+
+    /*
+     * The number of bits set among the 512.
+     */
+    fn count_ones(&self) -> u32 {
+        self.to_array().iter().map(|w| w.count_ones()).sum()
+    }
+
+    /*
+     * Swaps the bits whose positions differ only by j, j being a power of two less than 512:
+     * the bit i goes to the position i ^ j. Below 64, the bits are swapped within each u64 with
+     * shifts and masks, and above, the u64s themselves are swapped.
+     */
+    fn swap_lanes(&self, j: usize) -> u64x8 {
+        let w = self.to_array();
+        let mut words = [0; 8];
+
+        for k in 0..8 {
+            words[k] = if j < 64 {
+                let mask = !0 / ((1 << j) + 1);
+                ((w[k] >> j) & mask) | ((w[k] & mask) << j)
+            } else {
+                w[k ^ (j / 64)]
+            };
+        }
+
+        u64x8::from_array(words)
+    }
+
+    /*
+     * Rotates the 512 bits towards the low positions by n, which must be less than 512: the bit
+     * i + n modulo 512 goes to the position i. The u64s are rotated by n / 64, then each of
+     * them takes its high bits from the next one.
+     */
+    fn rotate_lanes(&self, n: usize) -> u64x8 {
+        let w = self.to_array();
+        let (q, r) = (n / 64, n % 64);
+        let mut words = [0; 8];
+
+        for k in 0..8 {
+            words[k] = if r == 0 {
+                w[(k + q) % 8]
+            } else {
+                (w[(k + q) % 8] >> r) | (w[(k + q + 1) % 8] << (64 - r))
+            };
+        }
+
+        u64x8::from_array(words)
+    }
+
+    /*
+     * A u64x8 with the bits set at the positions i such that i & j is 0, j being a power of
+     * two, with the same layout as a row of a u8x512.
+     */
+    fn lanes_with_bit_clear(j: usize) -> u64x8 {
+        let mut words = [0; 8];
+
+        for (k, word) in words.iter_mut().enumerate() {
+            *word = if j < 64 {
+                !0 / ((1 << j) + 1)
+            } else if k & (j / 64) == 0 {
+                !0
+            } else {
+                0
+            };
+        }
+
+        u64x8::from_array(words)
+    }
+
+    /*
+     * Creates a u64x8 with all of its 8 u64s set to value.
+     */
+    pub const fn splat(value: u64) -> u64x8 {
+        u64x8::from_array([value; 8])
+    }
+}
+
+/*
+ * Converts an array of 8 u64s to a u64x8, see from_array.
+ */
+impl From<[u64; 8]> for u64x8 {
+    #[inline(always)]
+    fn from(words: [u64; 8]) -> u64x8 {
+        u64x8::from_array(words)
+    }
+}
+
+/*
+ * Converts a u64x8 to an array of 8 u64s, see to_array.
+ */
+impl From<u64x8> for [u64; 8] {
+    #[inline(always)]
+    fn from(a: u64x8) -> [u64; 8] {
+        a.to_array()
+    }
+}
+
+/*
+ * Implementation of u64x8 ^ u64x8 -> u64x8.
+ */
+impl BitXor for u64x8 {
+    type Output = u64x8;
+
+    #[inline(always)]
+    fn bitxor(self, rhs: u64x8) -> u64x8 {
+        unsafe { simd_xor(self, rhs) }
+    }
+}
+
+/*
+ * Implementation of u64x8 & u64x8 -> u64x8.
+ */
+impl BitAnd for u64x8 {
+    type Output = u64x8;
+
+    #[inline(always)]
+    fn bitand(self, rhs: u64x8) -> u64x8 {
+        unsafe { simd_and(self, rhs) }
+    }
+}
+
+/*
+ * Implementation of u64x8 | u64x8 -> u64x8.
+ */
+impl BitOr for u64x8 {
+    type Output = u64x8;
+
+    #[inline(always)]
+    fn bitor(self, rhs: u64x8) -> u64x8 {
+        unsafe { simd_or(self, rhs) }
+    }
+}
+
+/*
+ * Implementation of !u64x8 -> u64x8, flipping all 512 bits.
+ */
+impl Not for u64x8 {
+    type Output = u64x8;
+
+    #[inline(always)]
+    fn not(self) -> u64x8 {
+        unsafe { simd_xor(self, u64x8::ONES) }
+    }
+}
+
+/*
+ * A struct holding 4 u64s, for a total of 256 bits. It's the width of the simd registers of
+ * most cpus, since only those with AVX-512 have 512-bit registers.
+ */
+#[cfg(not(any(miri, feature = "portable-simd", feature = "scalar-fallback")))]
+#[repr(simd)]
+#[derive(Clone, Copy)]
+pub struct u64x4(u64, u64, u64, u64);
+
+#[cfg(all(feature = "portable-simd", not(any(miri, feature = "scalar-fallback"))))]
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct u64x4(core::simd::u64x4);
+
+#[cfg(any(miri, feature = "scalar-fallback"))]
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct u64x4([u64; 4]);
+
+impl u64x4 {
+    /*
+     * The zero of a u64x4.
+     */
+    #[cfg(not(any(miri, feature = "portable-simd", feature = "scalar-fallback")))]
+    const ZERO: u64x4 = u64x4(0, 0, 0, 0);
+
+    #[cfg(all(feature = "portable-simd", not(any(miri, feature = "scalar-fallback"))))]
+    const ZERO: u64x4 = u64x4(core::simd::u64x4::from_array([0; 4]));
+
+    #[cfg(any(miri, feature = "scalar-fallback"))]
+    const ZERO: u64x4 = u64x4([0; 4]);
 }
 
 /*
  * Get the functions allowing the use of your cpu's simd capabilities.
  */
+#[cfg(not(any(miri, feature = "portable-simd", feature = "scalar-fallback")))]
 extern "platform-intrinsic" {
     fn simd_xor<u64x8>(a: u64x8, b: u64x8) -> u64x8;
     fn simd_and<u64x8>(a: u64x8, b: u64x8) -> u64x8;
     fn simd_or<u64x8>(a: u64x8, b: u64x8) -> u64x8;
 }
 
+/*
+ * Without the platform intrinsics, the same functions are given by the rows themselves, through
+ * the operators of core::simd or through loops over the u64s of the scalar fallback. They stay
+ * unsafe so that all the backends are called alike.
+ */
+#[cfg(any(miri, feature = "portable-simd", feature = "scalar-fallback"))]
+#[inline(always)]
+unsafe fn simd_xor<T: Row>(a: T, b: T) -> T {
+    a.xor(b)
+}
+
+#[cfg(any(miri, feature = "portable-simd", feature = "scalar-fallback"))]
+#[inline(always)]
+unsafe fn simd_and<T: Row>(a: T, b: T) -> T {
+    a.and(b)
+}
+
+#[cfg(any(miri, feature = "portable-simd", feature = "scalar-fallback"))]
+#[inline(always)]
+unsafe fn simd_or<T: Row>(a: T, b: T) -> T {
+    a.or(b)
+}
+
+/*
+ * A full adder over 512 independent bits. Returns the sum and the carry of a + b + c.
+ */
+#[inline(always)]
+unsafe fn full_add(a: u64x8, b: u64x8, c: u64x8) -> (u64x8, u64x8) {
+    let sum = simd_xor(simd_xor(a, b), c);
+    let carry = simd_or(simd_or(simd_and(a, b), simd_and(a, c)), simd_and(b, c));
+    (sum, carry)
+}
+
+/*
+ * Spreads the 32 bits of x over the even bits of a u64, the odd bits being zeros.
+ */
+fn spread_bits(x: u32) -> u64 {
+    let mut x = x as u64;
+
+    x = (x | x << 16) & 0x0000FFFF0000FFFF;
+    x = (x | x << 8) & 0x00FF00FF00FF00FF;
+    x = (x | x << 4) & 0x0F0F0F0F0F0F0F0F;
+    x = (x | x << 2) & 0x3333333333333333;
+    x = (x | x << 1) & 0x5555555555555555;
+
+    x
+}
+
+/*
+ * Gathers the 32 even bits of x into a u32, the inverse of spread_bits.
+ */
+fn compact_bits(x: u64) -> u32 {
+    let mut x = x & 0x5555555555555555;
+
+    x = (x | x >> 1) & 0x3333333333333333;
+    x = (x | x >> 2) & 0x0F0F0F0F0F0F0F0F;
+    x = (x | x >> 4) & 0x00FF00FF00FF00FF;
+    x = (x | x >> 8) & 0x0000FFFF0000FFFF;
+    x = (x | x >> 16) & 0x00000000FFFFFFFF;
+
+    x as u32
+}
+
+/*
+ * Transposes a 64x64 bit matrix in place, the bit v of the u64 i becoming the bit i of the u64
+ * v, given that only its first bits columns may be set, bits being a power of two. Only the
+ * first bits u64s of the result are computed, the others being left as they are.
+ *
+ * It's Eklundh's recursive transposition: the matrix is split in four blocks of 32x32 bits, the
+ * two off-diagonal blocks being swapped, then each block is split again, and so on down to
+ * blocks of a single bit. Each level is a pass swapping its blocks all at once with shifts and
+ * masks, and the passes can be done in any order. The passes over blocks of bits rows or more
+ * are done first: the columns they move from the first rows are zeros, so they merely gather
+ * the other rows into the first ones, and the remaining passes only have to deal with these.
+ */
+fn transpose_64x64(matrix: &mut [u64; 64], bits: usize) {
+    let mut span = 32;
+
+    while span >= bits {
+        for k in 0..span {
+            matrix[k] |= matrix[k + span] << span;
+        }
+
+        span >>= 1;
+    }
+
+    while span != 0 {
+        let mask = !0 / ((1 << span) + 1);
+
+        for k in 0..bits {
+            if k & span == 0 {
+                let swap = ((matrix[k] >> span) ^ matrix[k + span]) & mask;
+                matrix[k] ^= swap << span;
+                matrix[k + span] ^= swap;
+            }
+        }
+
+        span >>= 1;
+    }
+}
+
+/*
+ * The inverse of transpose_64x64: transposes a 64x64 bit matrix in place, given that only its
+ * first bits u64s may be set, bits being a power of two. All the 64 u64s of the result are
+ * computed, only their first bits columns being set.
+ *
+ * The passes of transpose_64x64 are undone in reverse order: first the swaps within the first
+ * bits rows, then the passes over blocks of bits rows or more, which now scatter the columns
+ * gathered in the first rows back to the other ones.
+ */
+fn untranspose_64x64(matrix: &mut [u64; 64], bits: usize) {
+    let mut span = 1;
+
+    while span < bits {
+        let mask = !0 / ((1 << span) + 1);
+
+        for k in 0..bits {
+            if k & span == 0 {
+                let swap = ((matrix[k] >> span) ^ matrix[k + span]) & mask;
+                matrix[k] ^= swap << span;
+                matrix[k + span] ^= swap;
+            }
+        }
+
+        span <<= 1;
+    }
+
+    while span != 64 {
+        let mask = !0 / ((1 << span) + 1);
+
+        for k in 0..span {
+            matrix[k + span] = (matrix[k] >> span) & mask;
+            matrix[k] &= mask;
+        }
+
+        span <<= 1;
+    }
+}
+
 //#################################################################################################
 //
-//                                      struct u8x128
+//                                       struct Bitsliced
 //
 //#################################################################################################
 
 /*
- * The struct defining a u8x512, 8 rows of u64x8s, for a total of 4096 bits.
+ * An integer type that can be a lane of a Bitsliced: u8, u16 or u32.
  */
-#[derive(Clone, Copy)]
-pub struct u8x512 {
-    rows: [u64x8; 8],
+pub trait Lane: Copy + Debug + Into<u64> {
+    /*
+     * The zero of the integer type.
+     */
+    const ZERO: Self;
+
+    /*
+     * Truncates a u64 to the integer type, keeping its low bits.
+     */
+    fn truncate(x: u64) -> Self;
 }
 
+macro_rules! lane {
+    ($($lane: ty)*) => {
+        $(
+            impl Lane for $lane {
+                const ZERO: $lane = 0;
+
+                #[inline(always)]
+                fn truncate(x: u64) -> $lane {
+                    x as $lane
+                }
+            }
+        )*
+    };
+}
+
+lane!(u8 u16 u32);
+
 /*
- * Converts a reference to an array of 512 u8s to a u8x512. The conversion is done through
- * "horizontalization". Think of it as a matrix transposition.
+ * A simd vector of u64s that can be a row of a Bitsliced: u64x8 or u64x4. Its u64s, called words,
+ * can be accessed by index, each of them holding 64 lanes.
  */
-impl From<&[u8; 512]> for u8x512 {
-    fn from(cols: &[u8; 512]) -> u8x512 {
-        let mut rows = [u64x8::ZERO; 8];
-        let mut h_mask: u64;
+pub trait Row: Copy {
+    /*
+     * The row with all of its bits cleared.
+     */
+    const ZERO: Self;
+
+    /*
+     * The word w of the row.
+     */
+    fn word(&self, w: usize) -> u64;
+
+    /*
+     * A mutable reference to the word w of the row.
+     */
+    fn word_mut(&mut self, w: usize) -> &mut u64;
+
+    /*
+     * The bitwise operations used without the platform intrinsics, see simd_xor, simd_and and
+     * simd_or.
+     */
+    #[cfg(any(miri, feature = "portable-simd", feature = "scalar-fallback"))]
+    fn xor(self, rhs: Self) -> Self;
+
+    #[cfg(any(miri, feature = "portable-simd", feature = "scalar-fallback"))]
+    fn and(self, rhs: Self) -> Self;
+
+    #[cfg(any(miri, feature = "portable-simd", feature = "scalar-fallback"))]
+    fn or(self, rhs: Self) -> Self;
+}
+
+#[cfg(not(any(miri, feature = "portable-simd", feature = "scalar-fallback")))]
+macro_rules! row {
+    ($simd: ident; $($word: tt)*) => {
+        impl Row for $simd {
+            const ZERO: $simd = $simd::ZERO;
+
+            #[inline(always)]
+            fn word(&self, w: usize) -> u64 {
+                match w {
+                    $($word => self.$word,)*
+                    _ => unreachable!(),
+                }
+            }
+
+            #[inline(always)]
+            fn word_mut(&mut self, w: usize) -> &mut u64 {
+                match w {
+                    $($word => &mut self.$word,)*
+                    _ => unreachable!(),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(all(feature = "portable-simd", not(any(miri, feature = "scalar-fallback"))))]
+macro_rules! row {
+    ($simd: ident; $($word: tt)*) => {
+        impl Row for $simd {
+            const ZERO: $simd = $simd::ZERO;
+
+            #[inline(always)]
+            fn word(&self, w: usize) -> u64 {
+                self.0[w]
+            }
+
+            #[inline(always)]
+            fn word_mut(&mut self, w: usize) -> &mut u64 {
+                &mut self.0[w]
+            }
+
+            #[inline(always)]
+            fn xor(self, rhs: $simd) -> $simd {
+                $simd(self.0 ^ rhs.0)
+            }
+
+            #[inline(always)]
+            fn and(self, rhs: $simd) -> $simd {
+                $simd(self.0 & rhs.0)
+            }
+
+            #[inline(always)]
+            fn or(self, rhs: $simd) -> $simd {
+                $simd(self.0 | rhs.0)
+            }
+        }
+    };
+}
+
+#[cfg(any(miri, feature = "scalar-fallback"))]
+macro_rules! row {
+    ($simd: ident; $($word: tt)*) => {
+        impl Row for $simd {
+            const ZERO: $simd = $simd::ZERO;
+
+            #[inline(always)]
+            fn word(&self, w: usize) -> u64 {
+                self.0[w]
+            }
+
+            #[inline(always)]
+            fn word_mut(&mut self, w: usize) -> &mut u64 {
+                &mut self.0[w]
+            }
+
+            #[inline(always)]
+            fn xor(mut self, rhs: $simd) -> $simd {
+                for (a, b) in self.0.iter_mut().zip(rhs.0.iter()) {
+                    *a ^= b;
+                }
+                self
+            }
+
+            #[inline(always)]
+            fn and(mut self, rhs: $simd) -> $simd {
+                for (a, b) in self.0.iter_mut().zip(rhs.0.iter()) {
+                    *a &= b;
+                }
+                self
+            }
+
+            #[inline(always)]
+            fn or(mut self, rhs: $simd) -> $simd {
+                for (a, b) in self.0.iter_mut().zip(rhs.0.iter()) {
+                    *a |= b;
+                }
+                self
+            }
+        }
+    };
+}
+
+row!(u64x8; 0 1 2 3 4 5 6 7);
+row!(u64x4; 0 1 2 3);
+
+/*
+ * The shape of a Bitsliced, its number of bits per lane and of lanes, only used to pick the
+ * types of its lanes and rows through the Layout trait.
+ */
+pub struct Shape<const BITS: usize, const LANES: usize>;
+
+/*
+ * The types of the lanes and of the rows of a Bitsliced of a given Shape. Only the shapes of the
+ * aliases below implement it.
+ */
+pub trait Layout {
+    type Lane: Lane;
+    type Row: Row;
+}
+
+impl Layout for Shape<8, 512> {
+    type Lane = u8;
+    type Row = u64x8;
+}
+
+impl Layout for Shape<16, 512> {
+    type Lane = u16;
+    type Row = u64x8;
+}
+
+impl Layout for Shape<32, 512> {
+    type Lane = u32;
+    type Row = u64x8;
+}
+
+impl Layout for Shape<8, 256> {
+    type Lane = u8;
+    type Row = u64x4;
+}
+
+/*
+ * LANES lanes of BITS bits each, stored as BITS rows: the row v holds the bit v of every lane,
+ * the lane i being the bit i%64 of the word i/64 of each row. The operations are thus done on
+ * all the lanes at once, a row at a time. The repr(transparent) makes its memory exactly the one
+ * of its rows.
+ */
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct Bitsliced<const BITS: usize, const LANES: usize>
+where
+    Shape<BITS, LANES>: Layout,
+{
+    rows: [<Shape<BITS, LANES> as Layout>::Row; BITS],
+}
+
+/*
+ * Converts a reference to an array of LANES lanes to a Bitsliced: the bit v of the lane i
+ * becomes the bit i of the row v. This is called "horizontalization", think of it as a matrix
+ * transposition. Each group of 64 lanes is zero-extended to a 64x64 bit matrix, which is
+ * transposed with transpose_64x64, its first BITS u64s being the words of the rows.
+ */
+impl<const BITS: usize, const LANES: usize> From<&[<Shape<BITS, LANES> as Layout>::Lane; LANES]>
+for Bitsliced<BITS, LANES>
+where
+    Shape<BITS, LANES>: Layout,
+{
+    fn from(cols: &[<Shape<BITS, LANES> as Layout>::Lane; LANES]) -> Bitsliced<BITS, LANES> {
+        let mut rows = [<Shape<BITS, LANES> as Layout>::Row::ZERO; BITS];
+        let mut matrix = [0; 64];
+
+        for w in 0..LANES / 64 {
+            for i in 0..64 {
+                matrix[i] = cols[w * 64 + i].into();
+            }
+
+            transpose_64x64(&mut matrix, BITS);
+
+            for v in 0..BITS {
+                *rows[v].word_mut(w) = matrix[v];
+            }
+        }
+
+        Bitsliced {rows}
+    }
+}
+
+/*
+ * Converts a Bitsliced to a boxed array of its lanes, see write_to for the algorithm. Needs the
+ * alloc feature.
+ */
+#[cfg(feature = "alloc")]
+impl<const BITS: usize, const LANES: usize>
+From<&Bitsliced<BITS, LANES>> for Box<[<Shape<BITS, LANES> as Layout>::Lane; LANES]>
+where
+    Shape<BITS, LANES>: Layout,
+{
+    fn from(
+        bitsliced: &Bitsliced<BITS, LANES>,
+    ) -> Box<[<Shape<BITS, LANES> as Layout>::Lane; LANES]> {
+        let mut cols = Box::new([<Shape<BITS, LANES> as Layout>::Lane::ZERO; LANES]);
+        bitsliced.write_to(&mut cols);
+        cols
+    }
+}
+
+/*
+ * Converts a Bitsliced to an array of its lanes, returned by value without allocating anything,
+ * see write_to for the algorithm.
+ */
+impl<const BITS: usize, const LANES: usize> From<&Bitsliced<BITS, LANES>>
+for [<Shape<BITS, LANES> as Layout>::Lane; LANES]
+where
+    Shape<BITS, LANES>: Layout,
+{
+    fn from(a: &Bitsliced<BITS, LANES>) -> [<Shape<BITS, LANES> as Layout>::Lane; LANES] {
+        let mut cols = [<Shape<BITS, LANES> as Layout>::Lane::ZERO; LANES];
+        a.write_to(&mut cols);
+        cols
+    }
+}
+
+/*
+ * Implementation of Debug for Bitsliced, formats it like a Vec of its lanes.
+ */
+impl<const BITS: usize, const LANES: usize> Debug for Bitsliced<BITS, LANES>
+where
+    Shape<BITS, LANES>: Layout,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut numbers = [<Shape<BITS, LANES> as Layout>::Lane::ZERO; LANES];
+        self.write_to(&mut numbers);
+        numbers.fmt(f)
+    }
+}
+
+/*
+ * Implementation of Default for Bitsliced, all of its lanes being 0. For a u8x512, it's its
+ * ZERO.
+ */
+impl<const BITS: usize, const LANES: usize> Default for Bitsliced<BITS, LANES>
+where
+    Shape<BITS, LANES>: Layout,
+{
+    fn default() -> Bitsliced<BITS, LANES> {
+        Bitsliced {rows: [<Shape<BITS, LANES> as Layout>::Row::ZERO; BITS]}
+    }
+}
+
+/*
+ * Implementation of &Bitsliced + &Bitsliced -> Bitsliced. It's an implementation of the binary
+ * long addition algorithm, done on all the lanes in parallel, wrapping around on overflow.
+ * Considering a simd operation as a single operation, the complexity is equal to
+ * 2+(BITS-1)x6+2 operations, 2+7x6+2 = 46 for a u8x512, giving a theoretical speedup of
+ * 512/46 = 11.13
+ */
+impl<const BITS: usize, const LANES: usize> Add for &Bitsliced<BITS, LANES>
+where
+    Shape<BITS, LANES>: Layout,
+{
+    type Output = Bitsliced<BITS, LANES>;
+
+    #[inline(always)]
+    fn add(self, rhs: &Bitsliced<BITS, LANES>) -> Bitsliced<BITS, LANES> {
+        let mut res = *self;
+        res += rhs;
+        res
+    }
+}
+
+/*
+ * Implementation of Bitsliced + Bitsliced -> Bitsliced.
+ */
+impl<const BITS: usize, const LANES: usize> Add for Bitsliced<BITS, LANES>
+where
+    Shape<BITS, LANES>: Layout,
+{
+    type Output = Bitsliced<BITS, LANES>;
+
+    #[inline(always)]
+    fn add(self, rhs: Bitsliced<BITS, LANES>) -> Bitsliced<BITS, LANES> {
+        Add::add(&self, &rhs)
+    }
+}
+
+/*
+ * Implementation of &mut Bitsliced += &Bitsliced, the same long addition, written in place.
+ */
+impl<const BITS: usize, const LANES: usize> AddAssign<&Bitsliced<BITS, LANES>>
+for Bitsliced<BITS, LANES>
+where
+    Shape<BITS, LANES>: Layout,
+{
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: &Bitsliced<BITS, LANES>) {
+        unsafe {
+            let mut tmp;
+            let mut carry = simd_and(self.rows[0], rhs.rows[0]);
+            self.rows[0] = simd_xor(self.rows[0], rhs.rows[0]);
+
+            for i in 1..BITS - 1 {
+                tmp = simd_xor(simd_xor(
+                    self.rows[i],
+                    rhs.rows[i]),
+                    carry,
+                );
+
+                carry = simd_or(simd_or(
+                    simd_and(self.rows[i], rhs.rows[i]),
+                    simd_and(self.rows[i], carry)),
+                    simd_and(rhs.rows[i], carry),
+                );
+
+                self.rows[i] = tmp;
+            }
+
+            self.rows[BITS - 1] = simd_xor(simd_xor(
+                self.rows[BITS - 1],
+                rhs.rows[BITS - 1]),
+                carry,
+            );
+        }
+    }
+}
+
+/*
+ * Implementation of &mut Bitsliced += Bitsliced.
+ */
+impl<const BITS: usize, const LANES: usize> AddAssign for Bitsliced<BITS, LANES>
+where
+    Shape<BITS, LANES>: Layout,
+{
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Bitsliced<BITS, LANES>) {
+        *self += &rhs;
+    }
+}
+
+/*
+ * Implementation of Sum for Bitsliced, folding the iterator with wrapping additions, starting
+ * from all lanes set to 0.
+ */
+impl<const BITS: usize, const LANES: usize> Sum for Bitsliced<BITS, LANES>
+where
+    Shape<BITS, LANES>: Layout,
+{
+    fn sum<I: Iterator<Item = Bitsliced<BITS, LANES>>>(iter: I) -> Bitsliced<BITS, LANES> {
+        iter.fold(Bitsliced::default(), |mut acc, x| {
+            acc += &x;
+            acc
+        })
+    }
+}
+
+/*
+ * Implementation of Sum<&Bitsliced> for Bitsliced.
+ */
+impl<'a, const BITS: usize, const LANES: usize> Sum<&'a Bitsliced<BITS, LANES>>
+for Bitsliced<BITS, LANES>
+where
+    Shape<BITS, LANES>: Layout,
+{
+    fn sum<I: Iterator<Item = &'a Bitsliced<BITS, LANES>>>(iter: I) -> Bitsliced<BITS, LANES> {
+        iter.fold(Bitsliced::default(), |mut acc, x| {
+            acc += x;
+            acc
+        })
+    }
+}
+
+/*
+ * Inherent methods shared by all the Bitsliced.
+ */
+impl<const BITS: usize, const LANES: usize> Bitsliced<BITS, LANES>
+where
+    Shape<BITS, LANES>: Layout,
+{
+    /*
+     * Writes the lanes of a Bitsliced into an array, without allocating anything. The
+     * conversion is done through "verticalization", the inverse of the horizontalization done
+     * by the conversion from an array: the bit i of the row v becomes the bit v of the lane i.
+     * The words w of the rows make a 64x64 bit matrix, which is transposed back with
+     * untranspose_64x64, its u64s being the lanes w * 64 to w * 64 + 63.
+     */
+    pub fn write_to(&self, out: &mut [<Shape<BITS, LANES> as Layout>::Lane; LANES]) {
+        let mut matrix = [0; 64];
+
+        for w in 0..LANES / 64 {
+            for (word, row) in matrix.iter_mut().zip(&self.rows) {
+                *word = row.word(w);
+            }
+
+            untranspose_64x64(&mut matrix, BITS);
+
+            for (col, &word) in out[w * 64..w * 64 + 64].iter_mut().zip(&matrix) {
+                *col = <Shape<BITS, LANES> as Layout>::Lane::truncate(word);
+            }
+        }
+    }
+}
+
+//#################################################################################################
+//
+//                                      struct u8x128
+//
+//#################################################################################################
+
+/*
+ * The type defining a u8x512, 8 rows of u64x8s, for a total of 4096 bits.
+ */
+pub type u8x512 = Bitsliced<8, 512>;
+
+/*
+ * Converts a u8 to a u8x512 with all of its lanes set to it, see splat.
+ */
+impl From<u8> for u8x512 {
+    #[inline(always)]
+    fn from(value: u8) -> u8x512 {
+        u8x512::splat(value)
+    }
+}
+
+/*
+ * Converts a slice of u8s to a u8x512, provided that it holds exactly 512 u8s. Otherwise, fails
+ * with a ConversionError telling the length of the slice.
+ */
+impl TryFrom<&[u8]> for u8x512 {
+    type Error = ConversionError;
+
+    fn try_from(slice: &[u8]) -> Result<u8x512, ConversionError> {
+        match <&[u8; 512]>::try_from(slice) {
+            Ok(cols) => Ok(u8x512::from(cols)),
+            Err(_) => Err(ConversionError::LengthMismatch {
+                expected: 512,
+                actual: slice.len(),
+            }),
+        }
+    }
+}
+
+/*
+ * Collects an iterator of exactly 512 u8s into a u8x512. Panics if the iterator yields fewer or
+ * more than 512 u8s.
+ */
+impl FromIterator<u8> for u8x512 {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> u8x512 {
+        let mut iter = iter.into_iter();
+        let mut cols = [0; 512];
+
+        for col in cols.iter_mut() {
+            *col = iter.next().expect("the iterator yielded fewer than 512 u8s");
+        }
+
+        assert!(iter.next().is_none(), "the iterator yielded more than 512 u8s");
+
+        u8x512::from(&cols)
+    }
+}
+
+/*
+ * Implementation of Display for u8x512, formats its 512 lanes separated by commas.
+ */
+impl Display for u8x512 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut cols = [0; 512];
+        self.write_to(&mut cols);
+
+        for (i, col) in cols.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            Display::fmt(col, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/*
+ * Implementation of LowerHex for u8x512, formats its 512 lanes as a string of 1024 lowercase
+ * hexadecimal digits, two per lane.
+ */
+impl LowerHex for u8x512 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut cols = [0; 512];
+        self.write_to(&mut cols);
+
+        for col in cols.iter() {
+            write!(f, "{:02x}", col)?;
+        }
+
+        Ok(())
+    }
+}
+
+/*
+ * Implementation of UpperHex for u8x512, same as LowerHex but with uppercase digits.
+ */
+impl UpperHex for u8x512 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut cols = [0; 512];
+        self.write_to(&mut cols);
+
+        for col in cols.iter() {
+            write!(f, "{:02X}", col)?;
+        }
+
+        Ok(())
+    }
+}
+
+/*
+ * Iterates over the 512 lanes of a u8x512, in order, the u8x512 being converted once into an
+ * array owned by the iterator.
+ */
+impl IntoIterator for u8x512 {
+    type Item = u8;
+    type IntoIter = core::array::IntoIter<u8, 512>;
+
+    fn into_iter(self) -> core::array::IntoIter<u8, 512> {
+        (&self).into_iter()
+    }
+}
+
+/*
+ * Same as IntoIterator for u8x512, for a reference.
+ */
+impl IntoIterator for &u8x512 {
+    type Item = u8;
+    type IntoIter = core::array::IntoIter<u8, 512>;
+
+    fn into_iter(self) -> core::array::IntoIter<u8, 512> {
+        let mut cols = [0; 512];
+        self.write_to(&mut cols);
+        IntoIterator::into_iter(cols)
+    }
+}
+
+/*
+ * Implementation of u8x512 == u8x512. Two u8x512s are equal if all of their 512 lanes are, that
+ * is if or-ing the xors of their rows gives zero.
+ */
+impl PartialEq for u8x512 {
+    fn eq(&self, other: &u8x512) -> bool {
+        unsafe {
+            let mut diff = simd_xor(self.rows[0], other.rows[0]);
+
+            for i in 1..8 {
+                diff = simd_or(diff, simd_xor(self.rows[i], other.rows[i]));
+            }
+
+            !Mask512(diff).any()
+        }
+    }
+}
+
+impl Eq for u8x512 {}
+
+/*
+ * Implementation of Hash for u8x512, hashing the u64s of its rows. Two equal u8x512s have the
+ * same rows, so they hash equally.
+ */
+impl Hash for u8x512 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for row in self.rows.iter() {
+            row.to_array().hash(state);
+        }
+    }
+}
+
+/*
+ * Serializes a u8x512 as the sequence of its 512 lanes, not as its rows, so that the
+ * serialized u8x512 reads like an array of 512 u8s. Needs the serde feature.
+ */
+#[cfg(feature = "serde")]
+impl serde::Serialize for u8x512 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut cols = [0; 512];
+        self.write_to(&mut cols);
+        serializer.collect_seq(cols.iter())
+    }
+}
+
+/*
+ * Deserializes a u8x512 from a sequence of exactly 512 u8s, converted through
+ * horizontalization. Needs the serde feature.
+ */
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for u8x512 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<u8x512, D::Error> {
+        struct LanesVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for LanesVisitor {
+            type Value = u8x512;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("a sequence of 512 u8s")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<u8x512, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut cols = [0; 512];
+
+                for i in 0..512 {
+                    cols[i] = seq.next_element()?
+                        .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+                }
+
+                if seq.next_element::<u8>()?.is_some() {
+                    return Err(serde::de::Error::invalid_length(513, &self));
+                }
+
+                Ok(u8x512::from(&cols))
+            }
+        }
+
+        deserializer.deserialize_seq(LanesVisitor)
+    }
+}
+
+/*
+ * Implementations of Zeroable and Pod for u8x512, so that slices of u8x512s can be cast from and
+ * to slices of bytes without copying anything. Beware: these bytes are the raw 4096 bits of the
+ * rows, not the 512 lanes, which are bitsliced across them. Casting the bytes of an array of
+ * u8s to a u8x512 doesn't horizontalize them, use From for that. Needs the bytemuck feature.
+ */
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for u8x512 {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for u8x512 {}
+
+/*
+ * Implementation of &u8x512 - &u8x512 -> u8x512. It's the binary long subtraction algorithm,
+ * the same as the addition but with a borrow propagated instead of a carry. Wraps around on
+ * underflow, like u8::wrapping_sub. The complexity is equal to 3+7x6+2 = 47 operations.
+ */
+impl Sub for &u8x512 {
+    type Output = u8x512;
+
+    fn sub(self, rhs: &u8x512) -> u8x512 {
+        unsafe {
+            let mut res = [u64x8::ZERO; 8];
+            let mut diff;
+
+            res[0] = simd_xor(self.rows[0], rhs.rows[0]);
+            let mut borrow = simd_and(simd_xor(self.rows[0], u64x8::ONES), rhs.rows[0]);
+
+            for ((res_row, &a), &b) in res[1..7].iter_mut()
+                .zip(&self.rows[1..7])
+                .zip(&rhs.rows[1..7])
+            {
+                diff = simd_xor(a, b);
+
+                *res_row = simd_xor(diff, borrow);
+
+                borrow = simd_or(
+                    simd_and(simd_xor(a, u64x8::ONES), b),
+                    simd_and(simd_xor(diff, u64x8::ONES), borrow),
+                );
+            }
+
+            res[7] = simd_xor(simd_xor(
+                self.rows[7],
+                rhs.rows[7]),
+                borrow,
+            );
+
+            u8x512 {rows: res}
+        }
+    }
+}
+
+/*
+ * Implementation of u8x512 - u8x512 -> u8x512.
+ */
+impl Sub for u8x512 {
+    type Output = u8x512;
+
+    #[inline(always)]
+    fn sub(self, rhs: u8x512) -> u8x512 {
+        Sub::sub(&self, &rhs)
+    }
+}
+
+/*
+ * Implementation of &mut u8x512 -= &u8x512.
+ */
+impl SubAssign<&u8x512> for u8x512 {
+    fn sub_assign(&mut self, rhs: &u8x512) {
+        unsafe {
+            let mut tmp;
+            let mut diff;
+            let mut borrow = simd_and(simd_xor(self.rows[0], u64x8::ONES), rhs.rows[0]);
+            self.rows[0] = simd_xor(self.rows[0], rhs.rows[0]);
+
+            for i in 1..7 {
+                diff = simd_xor(self.rows[i], rhs.rows[i]);
+                tmp = simd_xor(diff, borrow);
+
+                borrow = simd_or(
+                    simd_and(simd_xor(self.rows[i], u64x8::ONES), rhs.rows[i]),
+                    simd_and(simd_xor(diff, u64x8::ONES), borrow),
+                );
+
+                self.rows[i] = tmp;
+            }
+
+            self.rows[7] = simd_xor(simd_xor(
+                self.rows[7],
+                rhs.rows[7]),
+                borrow,
+            );
+        }
+    }
+}
+
+/*
+ * Implementation of &mut u8x512 -= u8x512.
+ */
+impl SubAssign for u8x512 {
+    fn sub_assign(&mut self, rhs: u8x512) {
+        *self -= &rhs;
+    }
+}
+
+/*
+ * Implementation of &u8x512 * &u8x512 -> u8x512. It's the shift-and-add multiplication
+ * algorithm: for each bit-plane j of rhs, self shifted left by j is masked by that plane (so
+ * only the lanes whose j-th bit is set keep it) and added to the accumulator. Shifting a
+ * u8x512 left by j is just moving its rows up by j, and since the shifted addend is zero
+ * in the j lower rows, the carry chain only has to start at row j. Wraps around on overflow,
+ * like u8::wrapping_mul.
+ */
+impl Mul for &u8x512 {
+    type Output = u8x512;
+
+    fn mul(self, rhs: &u8x512) -> u8x512 {
+        unsafe {
+            let mut acc = [u64x8::ZERO; 8];
+
+            for (acc_row, &row) in acc.iter_mut().zip(&self.rows) {
+                *acc_row = simd_and(row, rhs.rows[0]);
+            }
+
+            for j in 1..8 {
+                let mut addend;
+                let mut tmp;
+                let mut carry = u64x8::ZERO;
+
+                for (k, acc_row) in acc[..7].iter_mut().enumerate().skip(j) {
+                    addend = simd_and(self.rows[k - j], rhs.rows[j]);
+                    tmp = simd_xor(simd_xor(*acc_row, addend), carry);
+
+                    carry = simd_or(simd_or(
+                        simd_and(*acc_row, addend),
+                        simd_and(*acc_row, carry)),
+                        simd_and(addend, carry),
+                    );
+
+                    *acc_row = tmp;
+                }
+
+                addend = simd_and(self.rows[7 - j], rhs.rows[j]);
+                acc[7] = simd_xor(simd_xor(acc[7], addend), carry);
+            }
+
+            u8x512 {rows: acc}
+        }
+    }
+}
+
+/*
+ * Implementation of u8x512 * u8x512 -> u8x512.
+ */
+impl Mul for u8x512 {
+    type Output = u8x512;
+
+    #[inline(always)]
+    fn mul(self, rhs: u8x512) -> u8x512 {
+        Mul::mul(&self, &rhs)
+    }
+}
+
+/*
+ * Implementation of &mut u8x512 *= &u8x512. Every partial product needs the original rows of
+ * self, so they are copied before the accumulator overwrites them.
+ */
+impl MulAssign<&u8x512> for u8x512 {
+    fn mul_assign(&mut self, rhs: &u8x512) {
+        let lhs = *self;
+        self.rows = (&lhs * rhs).rows;
+    }
+}
+
+/*
+ * Implementation of &mut u8x512 *= u8x512.
+ */
+impl MulAssign for u8x512 {
+    fn mul_assign(&mut self, rhs: u8x512) {
+        *self *= &rhs;
+    }
+}
+
+/*
+ * Implementation of -&u8x512 -> u8x512. Computes the two's complement of each lane by inverting
+ * all the rows and then adding one, with a carry chain seeded with a carry in every lane.
+ * Behaves like u8::wrapping_neg.
+ */
+impl Neg for &u8x512 {
+    type Output = u8x512;
+
+    fn neg(self) -> u8x512 {
+        unsafe {
+            let mut rows = [u64x8::ZERO; 8];
+            let mut carry = u64x8::ONES;
+            let mut inv;
+
+            for (res_row, &row) in rows.iter_mut().zip(&self.rows) {
+                inv = simd_xor(row, u64x8::ONES);
+                *res_row = simd_xor(inv, carry);
+                carry = simd_and(inv, carry);
+            }
+
+            u8x512 {rows}
+        }
+    }
+}
+
+/*
+ * Implementation of -u8x512 -> u8x512.
+ */
+impl Neg for u8x512 {
+    type Output = u8x512;
+
+    #[inline(always)]
+    fn neg(self) -> u8x512 {
+        -&self
+    }
+}
+
+/*
+ * Implementation of &u8x512 ^ &u8x512 -> u8x512, a single simd xor per row.
+ */
+impl BitXor for &u8x512 {
+    type Output = u8x512;
+
+    fn bitxor(self, rhs: &u8x512) -> u8x512 {
+        let mut res = *self;
+        res ^= rhs;
+        res
+    }
+}
+
+/*
+ * Implementation of u8x512 ^ u8x512 -> u8x512.
+ */
+impl BitXor for u8x512 {
+    type Output = u8x512;
+
+    #[inline(always)]
+    fn bitxor(self, rhs: u8x512) -> u8x512 {
+        BitXor::bitxor(&self, &rhs)
+    }
+}
+
+/*
+ * Implementation of &mut u8x512 ^= &u8x512.
+ */
+impl BitXorAssign<&u8x512> for u8x512 {
+    fn bitxor_assign(&mut self, rhs: &u8x512) {
+        unsafe {
+            for i in 0..8 {
+                self.rows[i] = simd_xor(self.rows[i], rhs.rows[i]);
+            }
+        }
+    }
+}
+
+/*
+ * Implementation of &mut u8x512 ^= u8x512.
+ */
+impl BitXorAssign for u8x512 {
+    fn bitxor_assign(&mut self, rhs: u8x512) {
+        *self ^= &rhs;
+    }
+}
+
+/*
+ * Implementation of &u8x512 & &u8x512 -> u8x512, a single simd and per row.
+ */
+impl BitAnd for &u8x512 {
+    type Output = u8x512;
+
+    fn bitand(self, rhs: &u8x512) -> u8x512 {
+        let mut res = *self;
+        res &= rhs;
+        res
+    }
+}
+
+/*
+ * Implementation of u8x512 & u8x512 -> u8x512.
+ */
+impl BitAnd for u8x512 {
+    type Output = u8x512;
+
+    #[inline(always)]
+    fn bitand(self, rhs: u8x512) -> u8x512 {
+        BitAnd::bitand(&self, &rhs)
+    }
+}
+
+/*
+ * Implementation of &mut u8x512 &= &u8x512.
+ */
+impl BitAndAssign<&u8x512> for u8x512 {
+    fn bitand_assign(&mut self, rhs: &u8x512) {
+        unsafe {
+            for i in 0..8 {
+                self.rows[i] = simd_and(self.rows[i], rhs.rows[i]);
+            }
+        }
+    }
+}
+
+/*
+ * Implementation of &mut u8x512 &= u8x512.
+ */
+impl BitAndAssign for u8x512 {
+    fn bitand_assign(&mut self, rhs: u8x512) {
+        *self &= &rhs;
+    }
+}
+
+/*
+ * Implementation of &u8x512 | &u8x512 -> u8x512, a single simd or per row.
+ */
+impl BitOr for &u8x512 {
+    type Output = u8x512;
+
+    fn bitor(self, rhs: &u8x512) -> u8x512 {
+        let mut res = *self;
+        res |= rhs;
+        res
+    }
+}
+
+/*
+ * Implementation of u8x512 | u8x512 -> u8x512.
+ */
+impl BitOr for u8x512 {
+    type Output = u8x512;
+
+    #[inline(always)]
+    fn bitor(self, rhs: u8x512) -> u8x512 {
+        BitOr::bitor(&self, &rhs)
+    }
+}
+
+/*
+ * Implementation of &mut u8x512 |= &u8x512.
+ */
+impl BitOrAssign<&u8x512> for u8x512 {
+    fn bitor_assign(&mut self, rhs: &u8x512) {
+        unsafe {
+            for i in 0..8 {
+                self.rows[i] = simd_or(self.rows[i], rhs.rows[i]);
+            }
+        }
+    }
+}
+
+/*
+ * Implementation of &mut u8x512 |= u8x512.
+ */
+impl BitOrAssign for u8x512 {
+    fn bitor_assign(&mut self, rhs: u8x512) {
+        *self |= &rhs;
+    }
+}
+
+/*
+ * Implementation of !&u8x512 -> u8x512. There is no simd not, so every row is xor-ed with all
+ * ones instead.
+ */
+impl Not for &u8x512 {
+    type Output = u8x512;
+
+    fn not(self) -> u8x512 {
+        let mut res = *self;
+
+        unsafe {
+            for i in 0..8 {
+                res.rows[i] = simd_xor(res.rows[i], u64x8::ONES);
+            }
+        }
+
+        res
+    }
+}
+
+/*
+ * Implementation of !u8x512 -> u8x512.
+ */
+impl Not for u8x512 {
+    type Output = u8x512;
+
+    #[inline(always)]
+    fn not(self) -> u8x512 {
+        !&self
+    }
+}
+
+/*
+ * Inherent methods of u8x512.
+ */
+impl u8x512 {
+    /*
+     * The u8x512 with all of its lanes set to 0, the neutral element of the addition.
+     */
+    pub const ZERO: u8x512 = u8x512 {rows: [u64x8::ZERO; 8]};
+
+    /*
+     * The u8x512 with all of its lanes set to 0xFF.
+     */
+    pub const ONES: u8x512 = u8x512 {rows: [u64x8::ONES; 8]};
+
+    /*
+     * Creates a u8x512 whose lane i is f(i), like core::array::from_fn. The closure is called
+     * in order, from lane 0 to lane 511.
+     */
+    pub fn from_fn(mut f: impl FnMut(usize) -> u8) -> u8x512 {
+        let mut cols = [0; 512];
+
+        for (i, col) in cols.iter_mut().enumerate() {
+            *col = f(i);
+        }
+
+        u8x512::from(&cols)
+    }
+
+    /*
+     * Creates a u8x512 with all of its 512 lanes set to value. Each row is either all ones or
+     * all zeros depending on the corresponding bit of value, which is way cheaper than the
+     * conversion from an array.
+     */
+    pub fn splat(value: u8) -> u8x512 {
+        let mut rows = [u64x8::ZERO; 8];
+
+        for (i, row) in rows.iter_mut().enumerate() {
+            if value & (1 << i) != 0 {
+                *row = u64x8::ONES;
+            }
+        }
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Adds two u8x512s like the Add implementation does, but keeps the carry out of the last
+     * row instead of discarding it. Returns the wrapping sum along with a mask of the lanes
+     * whose addition overflowed.
+     */
+    pub fn carrying_add(&self, rhs: &u8x512) -> (u8x512, Mask512) {
+        unsafe {
+            let mut rows = [u64x8::ZERO; 8];
+
+            rows[0] = simd_xor(self.rows[0], rhs.rows[0]);
+            let mut carry = simd_and(self.rows[0], rhs.rows[0]);
+
+            for ((row, &a), &b) in rows[1..].iter_mut().zip(&self.rows[1..]).zip(&rhs.rows[1..]) {
+                *row = simd_xor(simd_xor(a, b), carry);
+
+                carry = simd_or(simd_or(
+                    simd_and(a, b),
+                    simd_and(a, carry)),
+                    simd_and(b, carry),
+                );
+            }
+
+            (u8x512 {rows}, Mask512(carry))
+        }
+    }
+
+    /*
+     * Adds two u8x512s, clamping the lanes that overflow to 0xFF instead of wrapping around,
+     * like u8::saturating_add. The overflow mask is simply or-ed into every row.
+     */
+    pub fn saturating_add(&self, rhs: &u8x512) -> u8x512 {
+        let (mut res, Mask512(carry)) = self.carrying_add(rhs);
+
+        unsafe {
+            for i in 0..8 {
+                res.rows[i] = simd_or(res.rows[i], carry);
+            }
+        }
+
+        res
+    }
+
+    /*
+     * Subtracts two u8x512s like the Sub implementation does, but keeps the borrow out of the
+     * last row instead of discarding it. Returns the wrapping difference along with a mask of
+     * the lanes whose subtraction underflowed.
+     */
+    pub fn borrowing_sub(&self, rhs: &u8x512) -> (u8x512, Mask512) {
+        unsafe {
+            let mut rows = [u64x8::ZERO; 8];
+            let mut diff;
+
+            rows[0] = simd_xor(self.rows[0], rhs.rows[0]);
+            let mut borrow = simd_and(simd_xor(self.rows[0], u64x8::ONES), rhs.rows[0]);
+
+            for ((row, &a), &b) in rows[1..].iter_mut().zip(&self.rows[1..]).zip(&rhs.rows[1..]) {
+                diff = simd_xor(a, b);
+
+                *row = simd_xor(diff, borrow);
+
+                borrow = simd_or(
+                    simd_and(simd_xor(a, u64x8::ONES), b),
+                    simd_and(simd_xor(diff, u64x8::ONES), borrow),
+                );
+            }
+
+            (u8x512 {rows}, Mask512(borrow))
+        }
+    }
+
+    /*
+     * Subtracts two u8x512s, clamping the lanes that underflow to 0 instead of wrapping around,
+     * like u8::saturating_sub. The rows are cleared wherever the borrow mask is set.
+     */
+    pub fn saturating_sub(&self, rhs: &u8x512) -> u8x512 {
+        let (mut res, Mask512(borrow)) = self.borrowing_sub(rhs);
+
+        unsafe {
+            let keep = simd_xor(borrow, u64x8::ONES);
+
+            for i in 0..8 {
+                res.rows[i] = simd_and(res.rows[i], keep);
+            }
+        }
+
+        res
+    }
+
+    /*
+     * Adds two u8x512s, with the adder of carrying_add. The returned mask tells which lanes
+     * overflowed, that is the lanes for which u8::checked_add would have returned None.
+     */
+    #[inline(always)]
+    pub fn checked_add(&self, rhs: &u8x512) -> (u8x512, Mask512) {
+        self.carrying_add(rhs)
+    }
+
+    /*
+     * Divides two u8x512s, like u8's division. Lanes divided by zero give 0xFF, like some
+     * hardware does, since a zero divisor always fits when trying to subtract it.
+     */
+    #[inline(always)]
+    pub fn div_lanes(&self, rhs: &u8x512) -> u8x512 {
+        self.div_rem(rhs).0
+    }
+
+    /*
+     * Computes the remainder of the division of two u8x512s, like u8's remainder. Lanes
+     * divided by zero keep the value of self, so that
+     * self == self.div_lanes(rhs) * rhs + self.rem_lanes(rhs) still holds in those lanes.
+     */
+    #[inline(always)]
+    pub fn rem_lanes(&self, rhs: &u8x512) -> u8x512 {
+        self.div_rem(rhs).1
+    }
+
+    /*
+     * Computes both the quotient and the remainder of two u8x512s with the restoring division
+     * algorithm. The bits of self are brought down in the remainder one at a time, from the
+     * highest, then rhs is subtracted from it in the lanes where it fits, those lanes getting
+     * the corresponding quotient bit set. Before subtracting, the remainder may need a ninth
+     * bit, kept aside in high: rhs always fits in the lanes where it is set.
+     */
+    fn div_rem(&self, rhs: &u8x512) -> (u8x512, u8x512) {
+        unsafe {
+            let mut quot = [u64x8::ZERO; 8];
+            let mut rem = u8x512::ZERO;
+
+            for i in (0..8).rev() {
+                let high = rem.rows[7];
+
+                for k in (1..8).rev() {
+                    rem.rows[k] = rem.rows[k - 1];
+                }
+
+                rem.rows[0] = self.rows[i];
+
+                let (diff, Mask512(borrow)) = rem.borrowing_sub(rhs);
+                let fits = simd_or(high, simd_xor(borrow, u64x8::ONES));
+
+                for k in 0..8 {
+                    rem.rows[k] = simd_xor(
+                        rem.rows[k],
+                        simd_and(simd_xor(rem.rows[k], diff.rows[k]), fits),
+                    );
+                }
+
+                quot[i] = fits;
+            }
+
+            (u8x512 {rows: quot}, rem)
+        }
+    }
+
+    /*
+     * Computes self * b + c, wrapping around on overflow. It's the same shift-and-add algorithm
+     * as the Mul implementation, but with the accumulator starting at c instead of zero, so the
+     * product is never materialized on its own.
+     */
+    pub fn mul_add(&self, b: &u8x512, c: &u8x512) -> u8x512 {
+        unsafe {
+            let mut acc = c.rows;
+
+            for j in 0..8 {
+                let mut addend;
+                let mut tmp;
+                let mut carry = u64x8::ZERO;
+
+                for (k, acc_row) in acc[..7].iter_mut().enumerate().skip(j) {
+                    addend = simd_and(self.rows[k - j], b.rows[j]);
+                    tmp = simd_xor(simd_xor(*acc_row, addend), carry);
+
+                    carry = simd_or(simd_or(
+                        simd_and(*acc_row, addend),
+                        simd_and(*acc_row, carry)),
+                        simd_and(addend, carry),
+                    );
+
+                    *acc_row = tmp;
+                }
+
+                addend = simd_and(self.rows[7 - j], b.rows[j]);
+                acc[7] = simd_xor(simd_xor(acc[7], addend), carry);
+            }
+
+            u8x512 {rows: acc}
+        }
+    }
+
+    /*
+     * Sums all 512 lanes together. Rather than converting back to an array, the bits set in each
+     * row are counted and weighted by the significance of the row.
+     */
+    pub fn reduce_sum(&self) -> u32 {
+        let mut sum = 0;
+
+        for i in 0..8 {
+            sum += self.rows[i].count_ones() << i;
+        }
+
+        sum
+    }
+
+    /*
+     * Adds one to every lane, wrapping around on overflow. Only needs a chain of half adders,
+     * seeded with a carry in every lane.
+     */
+    pub fn increment(&self) -> u8x512 {
+        let mut res = *self;
+        res.increment_assign();
+        res
+    }
+
+    /*
+     * Adds one to every lane in place, wrapping around on overflow.
+     */
+    pub fn increment_assign(&mut self) {
+        unsafe {
+            let mut tmp;
+            let mut carry = u64x8::ONES;
+
+            for i in 0..8 {
+                tmp = simd_and(self.rows[i], carry);
+                self.rows[i] = simd_xor(self.rows[i], carry);
+                carry = tmp;
+            }
+        }
+    }
+
+    /*
+     * Computes the rounded average (a + b + 1) >> 1 of every lane, like the pavgb x86
+     * instruction. The sum is carried on 9 bits, with a carry in every lane for the rounding:
+     * the first row of the sum is dropped, and the carry out becomes the last row.
+     */
+    pub fn avg(&self, rhs: &u8x512) -> u8x512 {
+        unsafe {
+            let mut rows = [u64x8::ZERO; 8];
+            let mut carry = simd_or(self.rows[0], rhs.rows[0]);
+
+            for i in 1..8 {
+                rows[i - 1] = simd_xor(simd_xor(
+                    self.rows[i],
+                    rhs.rows[i]),
+                    carry,
+                );
+
+                carry = simd_or(simd_or(
+                    simd_and(self.rows[i], rhs.rows[i]),
+                    simd_and(self.rows[i], carry)),
+                    simd_and(rhs.rows[i], carry),
+                );
+            }
+
+            rows[7] = carry;
+
+            u8x512 {rows}
+        }
+    }
+
+    /*
+     * Computes the absolute difference |a - b| of every lane. Both a - b and b - a are computed,
+     * and the borrow out of a - b tells in which lanes the second one must be kept.
+     */
+    pub fn abs_diff(&self, rhs: &u8x512) -> u8x512 {
+        let (mut res, Mask512(borrow)) = self.borrowing_sub(rhs);
+        let (rev, _) = rhs.borrowing_sub(self);
+
+        unsafe {
+            for i in 0..8 {
+                res.rows[i] = simd_xor(
+                    res.rows[i],
+                    simd_and(simd_xor(res.rows[i], rev.rows[i]), borrow),
+                );
+            }
+        }
+
+        res
+    }
+
+    /*
+     * Computes (a + b) % modulus in every lane, without overflowing in between. Both operands
+     * are first reduced modulo modulus, so that their sum is guaranteed to be less than twice
+     * modulus: the splatted modulus then only has to be subtracted once, in the lanes where the
+     * sum overflowed or is at least modulus. Panics if modulus is zero, like u8's remainder.
+     *
+     * The operands may be at or above modulus, which is why they are reduced first, but this
+     * costs two bitsliced divisions, by far the most expensive part. Operands already below
+     * modulus still pay for them.
+     */
+    pub fn add_mod(&self, rhs: &u8x512, modulus: u8) -> u8x512 {
+        assert!(modulus != 0, "attempt to calculate the remainder with a divisor of zero");
+
+        let modulus = u8x512::splat(modulus);
+        let (mut res, Mask512(carry)) =
+            self.rem_lanes(&modulus).carrying_add(&rhs.rem_lanes(&modulus));
+        let (diff, Mask512(borrow)) = res.borrowing_sub(&modulus);
+
+        unsafe {
+            let fits = simd_or(carry, simd_xor(borrow, u64x8::ONES));
+
+            for i in 0..8 {
+                res.rows[i] = simd_xor(
+                    res.rows[i],
+                    simd_and(simd_xor(res.rows[i], diff.rows[i]), fits),
+                );
+            }
+        }
+
+        res
+    }
+
+    /*
+     * Adds two u8x512s like the Add implementation does, but with a Kogge-Stone carry-lookahead
+     * adder instead of rippling the carry through the rows. The generate and propagate rows
+     * are combined over spans of 1, 2 then 4 rows, so the longest dependency chain grows with
+     * the logarithm of the number of rows instead of linearly, at the cost of a few more
+     * operations overall.
+     */
+    pub fn add_fast(&self, rhs: &u8x512) -> u8x512 {
+        unsafe {
+            let mut prop = [u64x8::ZERO; 8];
+            let mut gen = [u64x8::ZERO; 8];
+
+            for i in 0..8 {
+                prop[i] = simd_xor(self.rows[i], rhs.rows[i]);
+                gen[i] = simd_and(self.rows[i], rhs.rows[i]);
+            }
+
+            let mut group_prop = prop;
+
+            for &span in [1, 2, 4].iter() {
+                for i in (span..8).rev() {
+                    gen[i] = simd_or(gen[i], simd_and(group_prop[i], gen[i - span]));
+                    group_prop[i] = simd_and(group_prop[i], group_prop[i - span]);
+                }
+            }
+
+            let mut rows = prop;
+
+            for i in 1..8 {
+                rows[i] = simd_xor(prop[i], gen[i - 1]);
+            }
+
+            u8x512 {rows}
+        }
+    }
+
+    /*
+     * Adds two u8x512s like the Add implementation does, choosing the way to do it at runtime.
+     * If the cpu has AVX-512, the addition is compiled with the avx512f target feature
+     * enabled, so that each row fits in a single 512-bit register. Otherwise, each row is split
+     * in two u64x4s, and the addition is done on the two u8x256s they make, whose 256-bit rows
+     * fit in the registers of cpus with AVX2. The cpu is only queried on the first call, the
+     * answer being cached for the next ones. Without the std feature, the cpu can't be queried
+     * and AVX-512 is only used if the crate is compiled with the avx512f target feature. On
+     * architectures other than x86, the 256-bit path is always taken.
+     */
+    pub fn add_runtime(&self, rhs: &u8x512) -> u8x512 {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if u8x512::has_avx512f() {
+                return unsafe { self.add_avx512(rhs) };
+            }
+        }
+
+        self.add_tiled(rhs)
+    }
+
+    /*
+     * Whether the cpu has AVX-512, queried once and cached in a static: 0 while unknown, 1 if
+     * the cpu lacks AVX-512 and 2 if it has it.
+     */
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "std"))]
+    fn has_avx512f() -> bool {
+        use core::sync::atomic::{AtomicU8, Ordering};
+
+        static DETECTED: AtomicU8 = AtomicU8::new(0);
+
+        match DETECTED.load(Ordering::Relaxed) {
+            0 => {
+                let detected = is_x86_feature_detected!("avx512f");
+                DETECTED.store(1 + detected as u8, Ordering::Relaxed);
+                detected
+            }
+            cached => cached == 2,
+        }
+    }
+
+    /*
+     * Without the std feature, whether the crate is compiled with AVX-512.
+     */
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(feature = "std")))]
+    fn has_avx512f() -> bool {
+        cfg!(target_feature = "avx512f")
+    }
+
+    /// Adds two u8x512s like the Add implementation does, but compiled with the avx512f target
+    /// feature enabled, so that each row fits in a single 512-bit register without compiling
+    /// the whole crate with that target feature. The addition is inlined here, Add and
+    /// AddAssign being #[inline(always)]. It's the path taken by add_runtime on cpus with
+    /// AVX-512.
+    ///
+    /// # Safety
+    ///
+    /// The cpu running the code must have AVX-512, which can be checked with
+    /// is_x86_feature_detected!("avx512f"). Calling it on a cpu without AVX-512 is undefined
+    /// behavior, most likely crashing on an illegal instruction.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[target_feature(enable = "avx512f")]
+    pub unsafe fn add_avx512(&self, rhs: &u8x512) -> u8x512 {
+        self + rhs
+    }
+
+    /*
+     * The addition of add_runtime done on 256-bit rows: the lanes 0 to 255 and the lanes 256
+     * to 511 are added as two u8x256s.
+     */
+    fn add_tiled(&self, rhs: &u8x512) -> u8x512 {
+        let (lo, hi) = self.split_halves();
+        let (rhs_lo, rhs_hi) = rhs.split_halves();
+
+        u8x512::join_halves(&(lo + rhs_lo), &(hi + rhs_hi))
+    }
+
+    /*
+     * Splits a u8x512 into the u8x256 of its lanes 0 to 255 and the one of its lanes 256 to
+     * 511, each row being split into the u64x4 of its 4 low words, going to the first, and the
+     * one of its 4 high words, going to the second.
+     */
+    fn split_halves(&self) -> (u8x256, u8x256) {
+        let mut lo = [u64x4::ZERO; 8];
+        let mut hi = [u64x4::ZERO; 8];
+
+        for ((row, lo_row), hi_row) in self.rows.iter().zip(&mut lo).zip(&mut hi) {
+            let [l, h] = row.split();
+            *lo_row = l;
+            *hi_row = h;
+        }
+
+        (u8x256 {rows: lo}, u8x256 {rows: hi})
+    }
+
+    /*
+     * The inverse of split_halves.
+     */
+    fn join_halves(lo: &u8x256, hi: &u8x256) -> u8x512 {
+        let mut rows = [u64x8::ZERO; 8];
+
+        for ((row, &l), &h) in rows.iter_mut().zip(&lo.rows).zip(&hi.rows) {
+            *row = u64x8::join(l, h);
+        }
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Shifts every lane left by n bits, filling the low bits with zeros. This only moves the
+     * rows up by n, and shifting by 8 or more gives zero in every lane.
+     */
+    pub fn shl_lanes(&self, n: u32) -> u8x512 {
+        let n = n as usize;
+        let mut rows = [u64x8::ZERO; 8];
+
+        rows[n.min(8)..].copy_from_slice(&self.rows[..8 - n.min(8)]);
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Shifts every lane right by n bits, filling the high bits with zeros. This only moves the
+     * rows down by n, and shifting by 8 or more gives zero in every lane.
+     */
+    pub fn shr_lanes(&self, n: u32) -> u8x512 {
+        let n = n as usize;
+        let mut rows = [u64x8::ZERO; 8];
+
+        rows[..8 - n.min(8)].copy_from_slice(&self.rows[n.min(8)..]);
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Rotates the bits of every lane left by n, like u8::rotate_left. Lanes being 8 bits wide,
+     * this is a cyclic permutation of the rows by n modulo 8.
+     */
+    pub fn rotate_left(&self, n: u32) -> u8x512 {
+        let n = (n % 8) as usize;
+        let mut rows = [u64x8::ZERO; 8];
+
+        for i in 0..8 {
+            rows[(i + n) % 8] = self.rows[i];
+        }
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Rotates the bits of every lane right by n, like u8::rotate_right.
+     */
+    #[inline(always)]
+    pub fn rotate_right(&self, n: u32) -> u8x512 {
+        self.rotate_left(8 - n % 8)
+    }
+
+    /*
+     * Shifts every lane right by n bits as if it were an i8, filling the high bits with copies
+     * of the sign bit. The rows move down by n and the freed rows are copies of the last row.
+     * Shifting by 8 or more gives 0 or 0xFF depending on the sign of the lane, like shifting
+     * by 7 does.
+     */
+    pub fn shr_arithmetic(&self, n: u32) -> u8x512 {
+        let n = n.min(7) as usize;
+        let mut rows = [u64x8::ZERO; 8];
+
+        rows[..8 - n].copy_from_slice(&self.rows[n..]);
+        rows[8 - n..].fill(self.rows[7]);
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Reverses the order of the bits of every lane, like u8::reverse_bits. This is just the
+     * rows in reverse order.
+     */
+    pub fn reverse_bits(&self) -> u8x512 {
+        let mut rows = self.rows;
+        rows.reverse();
+        u8x512 {rows}
+    }
+
+    /*
+     * Computes self & !rhs in a single pass over the rows, without building !rhs first.
+     */
+    pub fn and_not(&self, rhs: &u8x512) -> u8x512 {
+        let mut rows = [u64x8::ZERO; 8];
+
+        unsafe {
+            for (i, row) in rows.iter_mut().enumerate() {
+                *row = simd_and(self.rows[i], simd_xor(rhs.rows[i], u64x8::ONES));
+            }
+        }
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Blends two u8x512s bit by bit, computing (self & mask) | (other & !mask): the bits set in
+     * mask are taken from self, and the others from other. It's computed as the equivalent
+     * other ^ ((self ^ other) & mask), which saves the not.
+     */
+    pub fn select_bits(&self, other: &u8x512, mask: &u8x512) -> u8x512 {
+        let mut rows = [u64x8::ZERO; 8];
+
+        unsafe {
+            for (i, row) in rows.iter_mut().enumerate() {
+                *row = simd_xor(
+                    other.rows[i],
+                    simd_and(simd_xor(self.rows[i], other.rows[i]), mask.rows[i]),
+                );
+            }
+        }
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Counts the number of bits set in every lane, like u8::count_ones. The 8 rows are summed
+     * together with a tree of full and half adders, each row counting as a single bit per lane.
+     * The count being at most 8, only the 4 lower rows of the result can be non zero.
+     */
+    pub fn count_ones(&self) -> u8x512 {
+        unsafe {
+            let r = &self.rows;
+            let mut rows = [u64x8::ZERO; 8];
+
+            let (s1, c1) = full_add(r[0], r[1], r[2]);
+            let (s2, c2) = full_add(r[3], r[4], r[5]);
+            let (s3, c3) = (simd_xor(r[6], r[7]), simd_and(r[6], r[7]));
+
+            let (bit0, c4) = full_add(s1, s2, s3);
+            let (s5, c5) = full_add(c1, c2, c3);
+            let (bit1, c6) = (simd_xor(s5, c4), simd_and(s5, c4));
+
+            rows[0] = bit0;
+            rows[1] = bit1;
+            rows[2] = simd_xor(c5, c6);
+            rows[3] = simd_and(c5, c6);
+
+            u8x512 {rows}
+        }
+    }
+
+    /*
+     * Counts the number of leading zeros of every lane, like u8::leading_zeros. The rows are
+     * scanned from the highest one, and the position of the first bit set in a lane is encoded
+     * into the result rows. Lanes with no bit set count 8 leading zeros.
+     */
+    pub fn leading_zeros(&self) -> u8x512 {
+        unsafe {
+            let mut rows = [u64x8::ZERO; 8];
+            let mut seen = u64x8::ZERO;
+
+            for i in (0..8).rev() {
+                let first = simd_and(self.rows[i], simd_xor(seen, u64x8::ONES));
+
+                for (bit, row) in rows[..3].iter_mut().enumerate() {
+                    if (7 - i) & (1 << bit) != 0 {
+                        *row = simd_or(*row, first);
+                    }
+                }
+
+                seen = simd_or(seen, self.rows[i]);
+            }
+
+            rows[3] = simd_xor(seen, u64x8::ONES);
+
+            u8x512 {rows}
+        }
+    }
+
+    /*
+     * Counts the number of trailing zeros of every lane, like u8::trailing_zeros. Same as
+     * leading_zeros, but the rows are scanned from the lowest one. Lanes with no bit set count
+     * 8 trailing zeros.
+     */
+    pub fn trailing_zeros(&self) -> u8x512 {
+        unsafe {
+            let mut rows = [u64x8::ZERO; 8];
+            let mut seen = u64x8::ZERO;
+
+            for i in 0..8 {
+                let first = simd_and(self.rows[i], simd_xor(seen, u64x8::ONES));
+
+                for (bit, row) in rows[..3].iter_mut().enumerate() {
+                    if i & (1 << bit) != 0 {
+                        *row = simd_or(*row, first);
+                    }
+                }
+
+                seen = simd_or(seen, self.rows[i]);
+            }
+
+            rows[3] = simd_xor(seen, u64x8::ONES);
+
+            u8x512 {rows}
+        }
+    }
+
+    /*
+     * Computes the parity of every lane, that is the xor of its 8 bits, by xor-ing the rows
+     * together. The bit of a lane is set in the returned mask if it has an odd number of bits
+     * set.
+     */
+    pub fn parity(&self) -> Mask512 {
+        let mut parity = self.rows[0];
+
+        unsafe {
+            for i in 1..8 {
+                parity = simd_xor(parity, self.rows[i]);
+            }
+        }
+
+        Mask512(parity)
+    }
+
+    /*
+     * Compares two u8x512s lane by lane. The bit of a lane is set in the returned mask if the
+     * lanes of self and rhs are equal, that is if none of the rows differ in that lane.
+     */
+    pub fn simd_eq(&self, rhs: &u8x512) -> Mask512 {
+        unsafe {
+            let mut diff = simd_xor(self.rows[0], rhs.rows[0]);
+
+            for i in 1..8 {
+                diff = simd_or(diff, simd_xor(self.rows[i], rhs.rows[i]));
+            }
+
+            Mask512(simd_xor(diff, u64x8::ONES))
+        }
+    }
+
+    /*
+     * Compares two u8x512s lane by lane. The bit of a lane is set in the returned mask if the
+     * lane of self is less than the lane of rhs, which is exactly when self - rhs underflows.
+     */
+    #[inline(always)]
+    pub fn simd_lt(&self, rhs: &u8x512) -> Mask512 {
+        self.borrowing_sub(rhs).1
+    }
+
+    /*
+     * Same as simd_lt, but for self > rhs.
+     */
+    #[inline(always)]
+    pub fn simd_gt(&self, rhs: &u8x512) -> Mask512 {
+        rhs.simd_lt(self)
+    }
+
+    /*
+     * Same as simd_lt, but for self <= rhs.
+     */
+    pub fn simd_le(&self, rhs: &u8x512) -> Mask512 {
+        let Mask512(gt) = self.simd_gt(rhs);
+        unsafe { Mask512(simd_xor(gt, u64x8::ONES)) }
+    }
+
+    /*
+     * Same as simd_lt, but for self >= rhs.
+     */
+    pub fn simd_ge(&self, rhs: &u8x512) -> Mask512 {
+        let Mask512(lt) = self.simd_lt(rhs);
+        unsafe { Mask512(simd_xor(lt, u64x8::ONES)) }
+    }
+
+    /*
+     * Returns the smaller of the two lanes of self and rhs, for each lane.
+     */
+    pub fn simd_min(&self, rhs: &u8x512) -> u8x512 {
+        self.simd_lt(rhs).select(self, rhs)
+    }
+
+    /*
+     * Returns the larger of the two lanes of self and rhs, for each lane.
+     */
+    pub fn simd_max(&self, rhs: &u8x512) -> u8x512 {
+        self.simd_lt(rhs).select(rhs, self)
+    }
+
+    /*
+     * Restricts every lane to the interval [lo, hi], like u8::clamp. Panics if lo > hi.
+     */
+    pub fn simd_clamp(&self, lo: u8, hi: u8) -> u8x512 {
+        assert!(lo <= hi, "lo must be less than or equal to hi");
+        self.simd_max(&u8x512::splat(lo)).simd_min(&u8x512::splat(hi))
+    }
+
+    /*
+     * Returns the index of the first lane equal to needle, like memchr, or None if there is
+     * none. The equality mask is computed against the splatted needle, and the index is found
+     * from the trailing zeros of its first non zero u64.
+     */
+    pub fn find(&self, needle: u8) -> Option<usize> {
+        let mask = self.simd_eq(&u8x512::splat(needle)).to_bitmask();
+
+        mask.iter()
+            .position(|&x| x != 0)
+            .map(|i| i * 64 + mask[i].trailing_zeros() as usize)
+    }
+
+    /*
+     * Returns the number of lanes equal to value, by counting the bits set in the equality
+     * mask against the splatted value.
+     */
+    #[inline(always)]
+    pub fn count_eq(&self, value: u8) -> u32 {
+        self.simd_eq(&u8x512::splat(value)).count()
+    }
+
+    /*
+     * Returns the value of the lane at index, gathering its 8 bits from the rows without
+     * converting the whole vector. Panics if index is not less than 512, like indexing an
+     * array would.
+     */
+    pub fn get(&self, index: usize) -> u8 {
+        assert!(index < 512, "index out of bounds: the len is 512 but the index is {}", index);
+
+        let bit = u64x8::lane_bit(index);
+        let mut value = 0;
+
+        unsafe {
+            for i in 0..8 {
+                if Mask512(simd_and(self.rows[i], bit)).any() {
+                    value |= 1 << i;
+                }
+            }
+        }
+
+        value
+    }
+
+    /*
+     * Sets the value of the lane at index in place, by clearing its bit in every row and then
+     * setting it back in the rows where value has a bit set. Panics if index is not less than
+     * 512, like indexing an array would.
+     */
+    pub fn set(&mut self, index: usize, value: u8) {
+        assert!(index < 512, "index out of bounds: the len is 512 but the index is {}", index);
+
+        let bit = u64x8::lane_bit(index);
+
+        unsafe {
+            let clear = simd_xor(bit, u64x8::ONES);
+
+            for i in 0..8 {
+                self.rows[i] = simd_and(self.rows[i], clear);
+
+                if value & (1 << i) != 0 {
+                    self.rows[i] = simd_or(self.rows[i], bit);
+                }
+            }
+        }
+    }
+
+    /*
+     * Returns an iterator over the 512 lanes, in order. It's the iterator of IntoIterator for
+     * &u8x512, the u8x512 being converted once into an array owned by the iterator.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = u8> {
+        self.into_iter()
+    }
+
+    /*
+     * Creates a u8x512 whose lane i is src[indices[i]]. The lanes are gathered into an array,
+     * which is then converted. Indices may repeat, and panics if one of them is not less than
+     * 512, like indexing src would.
+     */
+    pub fn gather(src: &[u8; 512], indices: &[usize; 512]) -> u8x512 {
+        let mut cols = [0; 512];
+
+        for i in 0..512 {
+            cols[i] = src[indices[i]];
+        }
+
+        u8x512::from(&cols)
+    }
+
+    /*
+     * Writes the lane i of self to out[indices[i]], the inverse of gather. If indices repeat,
+     * the last lane written wins, and out is left untouched where no index points. Panics if
+     * one of the indices is not less than 512, like indexing out would.
+     */
+    pub fn scatter(&self, indices: &[usize; 512], out: &mut [u8; 512]) {
+        let mut cols = [0; 512];
+        self.write_to(&mut cols);
+
+        for i in 0..512 {
+            out[indices[i]] = cols[i];
+        }
+    }
+
+    /*
+     * Reverses the order of the lanes, lane 0 becoming lane 511 and so on. As every row holds
+     * one bit of each lane, this is just reversing the 512 bits of every row.
+     */
+    pub fn reverse(&self) -> u8x512 {
+        let mut rows = [u64x8::ZERO; 8];
+
+        for (res_row, row) in rows.iter_mut().zip(&self.rows) {
+            *res_row = row.reverse_bits();
+        }
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Interleaves the lanes of two u8x512s. The first returned u8x512 holds the 256 lower lanes
+     * of both, and the second one the 256 upper lanes:
+     *
+     *   low[2 * i] = self[i]          low[2 * i + 1] = other[i]
+     *   high[2 * i] = self[256 + i]   high[2 * i + 1] = other[256 + i]
+     *
+     * for i in 0..256. Every row is interleaved on its own, the bits of self going to the even
+     * positions, and the bits of other to the odd ones.
+     */
+    pub fn interleave(&self, other: &u8x512) -> (u8x512, u8x512) {
+        let mut low = [u64x8::ZERO; 8];
+        let mut high = [u64x8::ZERO; 8];
+
+        for i in 0..8 {
+            let a = self.rows[i].to_array();
+            let b = other.rows[i].to_array();
+            let mut low_words = [0; 8];
+            let mut high_words = [0; 8];
+
+            for k in 0..8 {
+                let shift = 32 * (k % 2);
+
+                low_words[k] = spread_bits((a[k / 2] >> shift) as u32) |
+                    spread_bits((b[k / 2] >> shift) as u32) << 1;
+                high_words[k] = spread_bits((a[4 + k / 2] >> shift) as u32) |
+                    spread_bits((b[4 + k / 2] >> shift) as u32) << 1;
+            }
+
+            low[i] = u64x8::from_array(low_words);
+            high[i] = u64x8::from_array(high_words);
+        }
+
+        (u8x512 {rows: low}, u8x512 {rows: high})
+    }
+
+    /*
+     * Deinterleaves the lanes of two u8x512s, the inverse of interleave: self holds the low
+     * lanes and other the high lanes of the interleaving, and the two original u8x512s are
+     * returned.
+     */
+    pub fn deinterleave(&self, other: &u8x512) -> (u8x512, u8x512) {
+        let mut even = [u64x8::ZERO; 8];
+        let mut odd = [u64x8::ZERO; 8];
+
+        for i in 0..8 {
+            let low = self.rows[i].to_array();
+            let high = other.rows[i].to_array();
+            let mut even_words = [0; 8];
+            let mut odd_words = [0; 8];
+
+            for k in 0..4 {
+                even_words[k] = compact_bits(low[2 * k]) as u64 |
+                    (compact_bits(low[2 * k + 1]) as u64) << 32;
+                odd_words[k] = compact_bits(low[2 * k] >> 1) as u64 |
+                    (compact_bits(low[2 * k + 1] >> 1) as u64) << 32;
+                even_words[4 + k] = compact_bits(high[2 * k]) as u64 |
+                    (compact_bits(high[2 * k + 1]) as u64) << 32;
+                odd_words[4 + k] = compact_bits(high[2 * k] >> 1) as u64 |
+                    (compact_bits(high[2 * k + 1] >> 1) as u64) << 32;
+            }
+
+            even[i] = u64x8::from_array(even_words);
+            odd[i] = u64x8::from_array(odd_words);
+        }
+
+        (u8x512 {rows: even}, u8x512 {rows: odd})
+    }
+
+    /*
+     * Returns true if all of the lanes are 0, by or-ing the rows together.
+     */
+    pub fn is_zero(&self) -> bool {
+        let mut any = self.rows[0];
+
+        unsafe {
+            for i in 1..8 {
+                any = simd_or(any, self.rows[i]);
+            }
+        }
+
+        !Mask512(any).any()
+    }
+
+    /*
+     * Returns the row i of the u8x512, that is the bit i of all of its lanes, with the same
+     * layout as a Mask512. Panics if i is not less than 8.
+     */
+    pub fn plane(&self, i: usize) -> u64x8 {
+        assert!(i < 8, "plane index out of bounds: there are 8 planes but the index is {}", i);
+        self.rows[i]
+    }
+
+    /*
+     * Replaces the row i of the u8x512, that is the bit i of all of its lanes. Panics if i is
+     * not less than 8.
+     */
+    pub fn set_plane(&mut self, i: usize, plane: u64x8) {
+        assert!(i < 8, "plane index out of bounds: there are 8 planes but the index is {}", i);
+        self.rows[i] = plane;
+    }
+
+    /*
+     * Creates a u8x512 from its 8 rows, the plane i holding the bit i of all of the lanes, with
+     * the same layout as a Mask512. Nothing is transposed: this is for the code computing the
+     * planes directly.
+     */
+    pub const fn from_planes(planes: [u64x8; 8]) -> u8x512 {
+        u8x512 {rows: planes}
+    }
+
+    /*
+     * Returns the 8 rows of the u8x512, the inverse of from_planes.
+     */
+    pub fn into_planes(self) -> [u64x8; 8] {
+        self.rows
+    }
+
+    /*
+     * Zero-extends every lane to 16 bits, like `x as u16`. The 8 rows become the low rows of a
+     * u16x512, whose 8 high rows are zeros, so that sums of many u8s can be accumulated
+     * without overflowing.
+     */
+    pub fn widen(&self) -> u16x512 {
+        let mut rows = [u64x8::ZERO; 16];
+        rows[..8].copy_from_slice(&self.rows);
+        u16x512 {rows}
+    }
+
+    /*
+     * Computes the carryless product of every pair of lanes, like the CLMUL instruction does,
+     * the u8s being seen as polynomials over GF(2) whose product isn't reduced. It's the
+     * shift-and-xor multiplication algorithm: self shifted left by j, that is its rows moved up
+     * by j, is masked by the row j of rhs then xored into the rows j to j+7 of the result. Since
+     * this product has degree 14 at most, the row 15 of the result is zero.
+     */
+    pub fn clmul(&self, rhs: &u8x512) -> u16x512 {
+        let mut rows = [u64x8::ZERO; 16];
+
+        unsafe {
+            for j in 0..8 {
+                for i in 0..8 {
+                    rows[i + j] = simd_xor(rows[i + j], simd_and(self.rows[i], rhs.rows[j]));
+                }
+            }
+        }
+
+        u16x512 {rows}
+    }
+
+    /*
+     * Multiplies two u8x512s in GF(2^8), the field of AES and of most Reed-Solomon codes, whose
+     * u8s are polynomials over GF(2) reduced modulo the AES polynomial x^8+x^4+x^3+x+1 (0x11B).
+     * The carryless product is first computed with clmul, then its rows 14 to 8 are folded back
+     * from the highest one, since x^8 is x^4+x^3+x+1 modulo the polynomial. The complexity is
+     * equal to 64+64+7x4 = 156 operations.
+     */
+    pub fn gf_mul(&self, rhs: &u8x512) -> u8x512 {
+        let mut prod = self.clmul(rhs).rows;
+
+        unsafe {
+            for k in (8..15).rev() {
+                prod[k - 4] = simd_xor(prod[k - 4], prod[k]);
+                prod[k - 5] = simd_xor(prod[k - 5], prod[k]);
+                prod[k - 7] = simd_xor(prod[k - 7], prod[k]);
+                prod[k - 8] = simd_xor(prod[k - 8], prod[k]);
+            }
+        }
+
+        let mut rows = [u64x8::ZERO; 8];
+        rows.copy_from_slice(&prod[..8]);
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Computes the multiplicative inverse in GF(2^8) of every lane, the lanes set to 0 staying
+     * at 0. Since x^255 is 1 for every non-zero x of GF(2^8), the inverse is x^254, which is
+     * computed by exponentiation by squaring: squaring then multiplying by x six times gives
+     * x^127, squared a last time. This is the core of the S-box of AES.
+     */
+    pub fn gf_inverse(&self) -> u8x512 {
+        let mut res = *self;
+
+        for _ in 0..6 {
+            res = res.gf_mul(&res).gf_mul(self);
+        }
+
+        res.gf_mul(&res)
+    }
+
+    /*
+     * Applies the S-box of AES, the SubBytes step, to every lane. The lanes are inverted in
+     * GF(2^8), then go through the affine transformation of AES: the bit i of the result is the
+     * xor of the bits i, i+4, i+5, i+6 and i+7 modulo 8 of the inverse, and of the bit i of 0x63.
+     * Moving bits within the lanes is just picking rows, and xoring the constant is flipping the
+     * rows of the bits set in 0x63.
+     */
+    pub fn aes_sbox(&self) -> u8x512 {
+        let inv = self.gf_inverse();
+        let mut rows = [u64x8::ZERO; 8];
+
+        unsafe {
+            for (i, row) in rows.iter_mut().enumerate() {
+                *row = simd_xor(simd_xor(simd_xor(simd_xor(
+                    inv.rows[i],
+                    inv.rows[(i + 4) % 8]),
+                    inv.rows[(i + 5) % 8]),
+                    inv.rows[(i + 6) % 8]),
+                    inv.rows[(i + 7) % 8],
+                );
+
+                if (0x63 >> i) & 1 != 0 {
+                    *row = simd_xor(*row, u64x8::ONES);
+                }
+            }
+        }
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Counts the number of bits set among the 4096 bits of the vector. It's the Harley-Seal
+     * algorithm: the 8 rows are first compressed into the 4 rows of count_ones with its tree of
+     * carry-save adders, then only these 4 rows have to be popcounted, each weighted by its
+     * significance, instead of all the 8 rows.
+     */
+    pub fn total_ones(&self) -> u32 {
+        let counts = self.count_ones();
+        let mut total = 0;
+
+        for i in 0..4 {
+            total += counts.rows[i].count_ones() << i;
+        }
+
+        total
+    }
+
+    /*
+     * Counts the number of bits that differ between two u8x512s, that is the number of bits set
+     * in their xor.
+     */
+    pub fn hamming_distance(&self, other: &u8x512) -> u32 {
+        (self ^ other).total_ones()
+    }
+
+    /*
+     * Generates a pseudo-random u8x512 from a seed, which is advanced. Each word of the seed is
+     * the state of its own xorshift64 generator, the 8 of them being stepped together, and the
+     * 8 successive states are the rows of the result: since all bits of a state are as random
+     * as each other, there's no need to horizontalize anything. A word of the seed set to 0
+     * stays at 0, so that its 64 lanes are always 0: every word of the seed must be non-zero.
+     *
+     * Beware that xorshift64 is neither cryptographically secure nor of the best statistical
+     * quality: it fails the tests for linearity over GF(2), and the lanes of a same word come
+     * from adjacent bits of a same state. It's fast, and good enough for tests and benchmarks.
+     */
+    pub fn random(seed: &mut u64x8) -> u8x512 {
+        let mut state = seed.to_array();
+        let mut rows = [u64x8::ZERO; 8];
+
+        for row in rows.iter_mut() {
+            for x in state.iter_mut() {
+                *x ^= *x << 13;
+                *x ^= *x >> 7;
+                *x ^= *x << 17;
+            }
+
+            *row = u64x8::from_array(state);
+        }
+
+        *seed = u64x8::from_array(state);
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Replaces every lane x by table[x]. Each row b of the result is picked from the bits b of
+     * the 256 entries of the table with a balanced tree of multiplexers: the entries are paired
+     * by their index, and the row 0 of self selects between the two of each pair in every lane,
+     * then the row 1 between the results of the pairs of pairs, and so on up to the row 7. Since
+     * the leaves are constants, the first level is just picking rows among 0, the row 0, its
+     * negation and ones, which leaves 127 multiplexers of 3 operations per row of the result.
+     */
+    pub fn substitute(&self, table: &[u8; 256]) -> u8x512 {
+        let mut rows = [u64x8::ZERO; 8];
+
+        unsafe {
+            let leaves = [
+                u64x8::ZERO,
+                simd_xor(self.rows[0], u64x8::ONES),
+                self.rows[0],
+                u64x8::ONES,
+            ];
+
+            for (b, row) in rows.iter_mut().enumerate() {
+                let mut nodes = [u64x8::ZERO; 128];
+
+                for x in 0..128 {
+                    let lo = (table[2 * x] >> b) & 1;
+                    let hi = (table[2 * x + 1] >> b) & 1;
+                    nodes[x] = leaves[(lo | (hi << 1)) as usize];
+                }
+
+                for k in 1..8 {
+                    for x in 0..128 >> k {
+                        let lo = nodes[2 * x];
+                        let hi = nodes[2 * x + 1];
+                        nodes[x] = simd_xor(lo, simd_and(simd_xor(lo, hi), self.rows[k]));
+                    }
+                }
+
+                *row = nodes[0];
+            }
+        }
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Computes the bitwise majority of three u8x512s: each bit of the result is set if it is
+     * set in at least two of a, b and c, that is (a & b) | (a & c) | (b & c). It's the carry of
+     * a full adder, computed as (a & b) | (c & (a ^ b)) to save an operation per row.
+     */
+    pub fn majority(a: &u8x512, b: &u8x512, c: &u8x512) -> u8x512 {
+        let mut rows = [u64x8::ZERO; 8];
+
+        unsafe {
+            for (i, row) in rows.iter_mut().enumerate() {
+                *row = simd_or(
+                    simd_and(a.rows[i], b.rows[i]),
+                    simd_and(c.rows[i], simd_xor(a.rows[i], b.rows[i])),
+                );
+            }
+        }
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Sorts the 512 lanes in ascending order, with a bitonic sorting network. Its 45 passes
+     * each compare every lane i with the lane i ^ j, j being a power of two: the partners are
+     * brought face to face by swapping the bits of every row with swap_lanes, then each lane
+     * keeps either the min or the max of the pair, depending on which of the two it is and on
+     * whether its block of k lanes is sorted upward or downward. The network being the same for
+     * every input, the sort is branchless.
+     */
+    pub fn sort_lanes(&self) -> u8x512 {
+        let mut res = *self;
+        let mut k = 2;
+
+        while k <= 512 {
+            let mut j = k / 2;
+
+            while j != 0 {
+                let mut partner = res;
+
+                for i in 0..8 {
+                    partner.rows[i] = res.rows[i].swap_lanes(j);
+                }
+
+                let keep_min = unsafe {
+                    Mask512(simd_xor(simd_xor(
+                        u64x8::lanes_with_bit_clear(j),
+                        u64x8::lanes_with_bit_clear(k)),
+                        u64x8::ONES,
+                    ))
+                };
+
+                res = keep_min.select(&res.simd_min(&partner), &res.simd_max(&partner));
+                j /= 2;
+            }
+
+            k *= 2;
+        }
+
+        res
+    }
+
+    /*
+     * Counts the occurrences of each of the 256 values of a u8 among the lanes. The lanes are
+     * converted to an array, then tallied one after the other. Building the 256 equality masks
+     * with a tree of ands over the rows, then counting the bits set in each of them, would do
+     * more work: it needs 510 ands and 256 popcounts of 512 bits, whereas the transposition
+     * only takes a few dozens of shifts and masks per group of 64 lanes, plus the 512
+     * increments of the tally.
+     */
+    pub fn histogram(&self) -> [u32; 256] {
+        let mut counts = [0; 256];
+
+        for &x in <[u8; 512]>::from(self).iter() {
+            counts[x as usize] += 1;
+        }
+
+        counts
+    }
+
+    /*
+     * Creates a u8x512 from the first 512 u8s of data, the lanes past the end of data being set
+     * to 0 if it holds fewer than 512 u8s, and the u8s past the first 512 being ignored if it
+     * holds more.
+     */
+    pub fn from_slice_padded(data: &[u8]) -> u8x512 {
+        let len = data.len().min(512);
+        let mut cols = [0; 512];
+        cols[..len].copy_from_slice(&data[..len]);
+        u8x512::from(&cols)
+    }
+
+    /*
+     * Returns the first len lanes of the u8x512 in a Vec, the inverse of from_slice_padded for a
+     * slice of len u8s. Panics if len is greater than 512. Needs the alloc feature.
+     */
+    #[cfg(feature = "alloc")]
+    pub fn to_vec_trimmed(&self, len: usize) -> Vec<u8> {
+        assert!(len <= 512, "to_vec_trimmed: len is {} but a u8x512 only has 512 lanes", len);
+        <[u8; 512]>::from(self)[..len].to_vec()
+    }
+
+    /// Reads 512 u8s from ptr, which doesn't need to be aligned, and converts them to a
+    /// u8x512 like the conversion from an array does. This is for the memory handed over by FFI
+    /// or memory-mapped files, without copying it into an array first.
+    ///
+    /// # Safety
+    ///
+    /// ptr must be valid for reads of 512 bytes, which must all be initialized, as for
+    /// core::ptr::read_unaligned of a [u8; 512].
+    pub unsafe fn load_unaligned(ptr: *const u8) -> u8x512 {
+        let cols = core::ptr::read_unaligned(ptr as *const [u8; 512]);
+        u8x512::from(&cols)
+    }
+
+    /// Converts the u8x512 to 512 u8s like write_to does, and writes them to ptr, which doesn't
+    /// need to be aligned.
+    ///
+    /// # Safety
+    ///
+    /// ptr must be valid for writes of 512 bytes, as for core::ptr::write_unaligned of a
+    /// [u8; 512].
+    pub unsafe fn store_unaligned(&self, ptr: *mut u8) {
+        core::ptr::write_unaligned(ptr as *mut [u8; 512], <[u8; 512]>::from(self));
+    }
+
+    /*
+     * Rotates the positions of the lanes, not their bits, to the left by n: the lane i moves to
+     * the position i - n modulo 512, like the rotate_left of slices. As every row holds one bit
+     * of each lane, this is rotating the 512 bits of every row.
+     */
+    pub fn rotate_lanes_left(&self, n: usize) -> u8x512 {
+        let n = n % 512;
+        let mut rows = [u64x8::ZERO; 8];
+
+        for (dst, src) in rows.iter_mut().zip(&self.rows) {
+            *dst = src.rotate_lanes(n);
+        }
+
+        u8x512 {rows}
+    }
+
+    /*
+     * Adds rhs to self only in the lanes where the mask is set, the other lanes staying equal
+     * to self. The sum is computed in every lane, then blended with self through the mask, so
+     * that no lane is treated differently from the others.
+     */
+    pub fn add_masked(&self, rhs: &u8x512, mask: &Mask512) -> u8x512 {
+        mask.select(&(self + rhs), self)
+    }
+}
+
+//#################################################################################################
+//
+//                                        struct Mask512
+//
+//#################################################################################################
+
+/*
+ * A mask of 512 bits, one per lane of a u8x512. The bit of lane i is the bit i % 64 of the
+ * (i / 64)-th u64 of the u64x8, which is the same layout as a single row of a u8x512.
+ */
+#[derive(Clone, Copy)]
+pub struct Mask512(u64x8);
+
+impl Mask512 {
+    /*
+     * Returns the mask as 8 u64s, lane i being the bit i % 64 of the (i / 64)-th u64.
+     */
+    pub fn to_bitmask(&self) -> [u64; 8] {
+        self.0.to_array()
+    }
+
+    /*
+     * Returns true if at least one of the 512 bits is set.
+     */
+    pub fn any(&self) -> bool {
+        self.0.to_array().iter().any(|&w| w != 0)
+    }
+
+    /*
+     * Returns the number of bits set, that is the number of lanes selected by the mask.
+     */
+    pub fn count(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /*
+     * Returns true if all of the 512 bits are set.
+     */
+    pub fn all(&self) -> bool {
+        self.0.to_array().iter().all(|&w| w == !0)
+    }
+
+    /*
+     * Blends two u8x512s lane by lane: the lanes where the mask is set are taken from a, and
+     * the others from b.
+     */
+    #[inline(always)]
+    pub fn select(&self, a: &u8x512, b: &u8x512) -> u8x512 {
+        a.select_bits(b, &self.broadcast())
+    }
+
+    /*
+     * Creates a u8x512 whose lanes are 0xFF where the mask is set, and 0 elsewhere, by copying
+     * the mask into every row. It can then be used as a mask for the bitwise operations.
+     */
+    fn broadcast(&self) -> u8x512 {
+        u8x512 {rows: [self.0; 8]}
+    }
+}
+
+//#################################################################################################
+//
+//                                     enum ConversionError
+//
+//#################################################################################################
+
+/*
+ * The error returned when a conversion to a u8x512 fails.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConversionError {
+    /*
+     * The source did not hold the expected number of u8s.
+     */
+    LengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ConversionError::LengthMismatch {expected, actual} => {
+                write!(f, "expected a length of {}, got a length of {}", expected, actual)
+            }
+        }
+    }
+}
+
+impl Error for ConversionError {}
+
+//#################################################################################################
+//
+//                                    other bitsliced types
+//
+//#################################################################################################
+
+/*
+ * The type defining a u16x512, 16 rows of u64x8s, for a total of 8192 bits.
+ */
+pub type u16x512 = Bitsliced<16, 512>;
+
+/*
+ * Inherent methods of u16x512.
+ */
+impl u16x512 {
+    /*
+     * Narrows every lane to 8 bits, clamping the lanes above 0xFF to 0xFF, like
+     * `x.min(255) as u8`. A lane is clamped when any of its 8 high rows is set, in which case
+     * the overflow mask is or-ed into its 8 low rows, which are kept otherwise.
+     */
+    pub fn narrow_saturating(&self) -> u8x512 {
+        unsafe {
+            let mut overflow = self.rows[8];
+
+            for i in 9..16 {
+                overflow = simd_or(overflow, self.rows[i]);
+            }
+
+            let mut rows = [u64x8::ZERO; 8];
+
+            for (res_row, &row) in rows.iter_mut().zip(&self.rows[..8]) {
+                *res_row = simd_or(row, overflow);
+            }
+
+            u8x512 {rows}
+        }
+    }
+}
+
+/*
+ * The type defining a u32x512, 32 rows of u64x8s, for a total of 16384 bits.
+ */
+pub type u32x512 = Bitsliced<32, 512>;
+
+/*
+ * The type defining a u8x256, 8 rows of u64x4s, for a total of 2048 bits. Its 256-bit rows
+ * fit in the simd registers of cpus with AVX2 but without AVX-512, which would otherwise have to
+ * split every operation on a u64x8 in two.
+ */
+pub type u8x256 = Bitsliced<8, 256>;
+
+//#################################################################################################
+//
+//                                        struct u8x1024
+//
+//#################################################################################################
+
+/*
+ * The struct defining a u8x1024, two u8x512s side by side, for a total of 8192 bits. The lanes
+ * are independent, so the two halves never interact and are simply processed one after the
+ * other, which amortizes the overhead of the transpositions over a full KiB of u8s.
+ */
+#[derive(Clone, Copy)]
+pub struct u8x1024 {
+    halves: [u8x512; 2],
+}
+
+/*
+ * Converts a reference to an array of 1024 u8s to a u8x1024, by converting each half of the
+ * array to a u8x512.
+ */
+impl From<&[u8; 1024]> for u8x1024 {
+    fn from(cols: &[u8; 1024]) -> u8x1024 {
+        let lo = <&[u8; 512]>::try_from(&cols[..512]).unwrap();
+        let hi = <&[u8; 512]>::try_from(&cols[512..]).unwrap();
+
+        u8x1024 {halves: [u8x512::from(lo), u8x512::from(hi)]}
+    }
+}
+
+/*
+ * Converts a u8x1024 to a boxed array of 1024 u8s, see write_to. Needs the alloc feature.
+ */
+#[cfg(feature = "alloc")]
+impl From<&u8x1024> for Box<[u8; 1024]> {
+    fn from(a: &u8x1024) -> Box<[u8; 1024]> {
+        let mut cols = Box::new([0; 1024]);
+        a.write_to(&mut cols);
+        cols
+    }
+}
+
+/*
+ * Implementation of Debug for u8x1024, formats it like a Vec<u8>.
+ */
+impl Debug for u8x1024 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut numbers = [0; 1024];
+        self.write_to(&mut numbers);
+        numbers.fmt(f)
+    }
+}
+
+/*
+ * Implementation of &u8x1024 + &u8x1024 -> u8x1024, two additions of u8x512s.
+ */
+impl Add for &u8x1024 {
+    type Output = u8x1024;
+
+    fn add(self, rhs: &u8x1024) -> u8x1024 {
+        u8x1024 {halves: [
+            self.halves[0] + rhs.halves[0],
+            self.halves[1] + rhs.halves[1],
+        ]}
+    }
+}
+
+/*
+ * Implementation of u8x1024 + u8x1024 -> u8x1024.
+ */
+impl Add for u8x1024 {
+    type Output = u8x1024;
+
+    #[inline(always)]
+    fn add(self, rhs: u8x1024) -> u8x1024 {
+        Add::add(&self, &rhs)
+    }
+}
+
+/*
+ * Implementation of &mut u8x1024 += &u8x1024.
+ */
+impl AddAssign<&u8x1024> for u8x1024 {
+    fn add_assign(&mut self, rhs: &u8x1024) {
+        self.halves[0] += &rhs.halves[0];
+        self.halves[1] += &rhs.halves[1];
+    }
+}
+
+/*
+ * Implementation of &mut u8x1024 += u8x1024.
+ */
+impl AddAssign for u8x1024 {
+    fn add_assign(&mut self, rhs: u8x1024) {
+        *self += &rhs;
+    }
+}
+
+/*
+ * Inherent methods of u8x1024.
+ */
+impl u8x1024 {
+    /*
+     * Writes the 1024 lanes of a u8x1024 into an array of u8s, without allocating anything,
+     * each half being written in its half of the array.
+     */
+    pub fn write_to(&self, out: &mut [u8; 1024]) {
+        for (half, out) in self.halves.iter().zip(out.chunks_exact_mut(512)) {
+            half.write_to(<&mut [u8; 512]>::try_from(out).unwrap());
+        }
+    }
+}
+
+//#################################################################################################
+//
+//                                        struct i8x512
+//
+//#################################################################################################
+
+/*
+ * The struct defining an i8x512, a u8x512 whose lanes are read as two's complement i8s. The bits
+ * are laid out exactly the same, so the wrapping additions are the ones of a u8x512, only the
+ * comparisons and the saturations differ, because of the sign bit held in the last row.
+ */
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct i8x512(u8x512);
+
+/*
+ * Converts a reference to an array of 512 i8s to an i8x512, through the conversion of their
+ * bits to a u8x512.
+ */
+impl From<&[i8; 512]> for i8x512 {
+    fn from(lanes: &[i8; 512]) -> i8x512 {
+        let mut cols = [0; 512];
+
+        for i in 0..512 {
+            cols[i] = lanes[i] as u8;
+        }
+
+        i8x512(u8x512::from(&cols))
+    }
+}
+
+/*
+ * Converts an i8x512 to a boxed array of i8s, see write_to. Needs the alloc feature.
+ */
+#[cfg(feature = "alloc")]
+impl From<&i8x512> for Box<[i8; 512]> {
+    fn from(a: &i8x512) -> Box<[i8; 512]> {
+        let mut lanes = Box::new([0; 512]);
+        a.write_to(&mut lanes);
+        lanes
+    }
+}
+
+/*
+ * Reads the lanes of a u8x512 as i8s, without changing their bits.
+ */
+impl From<u8x512> for i8x512 {
+    fn from(unsigned: u8x512) -> i8x512 {
+        i8x512(unsigned)
+    }
+}
+
+/*
+ * Reads the lanes of an i8x512 as u8s, without changing their bits.
+ */
+impl From<i8x512> for u8x512 {
+    fn from(signed: i8x512) -> u8x512 {
+        signed.0
+    }
+}
+
+/*
+ * Implementation of Debug for i8x512, formats it like a Vec<i8>.
+ */
+impl Debug for i8x512 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut numbers = [0; 512];
+        self.write_to(&mut numbers);
+        numbers.fmt(f)
+    }
+}
+
+/*
+ * Implementation of &i8x512 + &i8x512 -> i8x512, wrapping around on overflow like
+ * i8::wrapping_add. It's the addition of a u8x512, two's complement making no difference.
+ */
+impl Add for &i8x512 {
+    type Output = i8x512;
+
+    #[inline(always)]
+    fn add(self, rhs: &i8x512) -> i8x512 {
+        i8x512(self.0 + rhs.0)
+    }
+}
+
+/*
+ * Implementation of i8x512 + i8x512 -> i8x512.
+ */
+impl Add for i8x512 {
+    type Output = i8x512;
+
+    #[inline(always)]
+    fn add(self, rhs: i8x512) -> i8x512 {
+        Add::add(&self, &rhs)
+    }
+}
+
+/*
+ * Implementation of &mut i8x512 += &i8x512.
+ */
+impl AddAssign<&i8x512> for i8x512 {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: &i8x512) {
+        self.0 += &rhs.0;
+    }
+}
+
+/*
+ * Implementation of &mut i8x512 += i8x512.
+ */
+impl AddAssign for i8x512 {
+    fn add_assign(&mut self, rhs: i8x512) {
+        *self += &rhs;
+    }
+}
+
+/*
+ * Inherent methods of i8x512.
+ */
+impl i8x512 {
+    /*
+     * Writes the 512 lanes of an i8x512 into an array of i8s, without allocating anything,
+     * through the conversion of its u8x512.
+     */
+    pub fn write_to(&self, out: &mut [i8; 512]) {
+        let mut cols = [0; 512];
+        self.0.write_to(&mut cols);
+
+        for i in 0..512 {
+            out[i] = cols[i] as i8;
+        }
+    }
+
+    /*
+     * The u8x512 whose lanes are the lanes of self plus 128, so that they compare as u8s the
+     * way the lanes of self compare as i8s. Adding 128 only flips the sign bit.
+     */
+    fn biased(&self) -> u8x512 {
+        let mut res = self.0;
+        res.rows[7] = unsafe { simd_xor(res.rows[7], u64x8::ONES) };
+        res
+    }
+
+    /*
+     * The lanes that must be saturated, where an addition or a subtraction overflowed, get
+     * i8::MIN if the lane of self is negative and i8::MAX otherwise, since the overflow always
+     * goes the way of the sign of self.
+     */
+    fn saturate(&self, res: u8x512, overflow: u64x8) -> i8x512 {
+        let sat = u8x512::splat(0x7F) ^ Mask512(self.0.rows[7]).broadcast();
+        i8x512(Mask512(overflow).select(&sat, &res))
+    }
+
+    /*
+     * Compares two i8x512s lane by lane, with signed semantics. The bit of a lane is set in the
+     * returned mask if the lane of self is less than the lane of rhs.
+     */
+    pub fn simd_lt(&self, rhs: &i8x512) -> Mask512 {
+        self.biased().simd_lt(&rhs.biased())
+    }
+
+    /*
+     * Same as simd_lt, but for self > rhs.
+     */
+    #[inline(always)]
+    pub fn simd_gt(&self, rhs: &i8x512) -> Mask512 {
+        rhs.simd_lt(self)
+    }
+
+    /*
+     * Returns the smaller of the two lanes of self and rhs, for each lane.
+     */
+    pub fn simd_min(&self, rhs: &i8x512) -> i8x512 {
+        i8x512(self.simd_lt(rhs).select(&self.0, &rhs.0))
+    }
+
+    /*
+     * Returns the larger of the two lanes of self and rhs, for each lane.
+     */
+    pub fn simd_max(&self, rhs: &i8x512) -> i8x512 {
+        i8x512(self.simd_lt(rhs).select(&rhs.0, &self.0))
+    }
+
+    /*
+     * Adds two i8x512s, clamping the lanes that overflow to i8::MIN or i8::MAX, like
+     * i8::saturating_add. A lane overflows when both of its operands have the same sign and
+     * the sign of the sum differs.
+     */
+    pub fn saturating_add(&self, rhs: &i8x512) -> i8x512 {
+        let res = self.0 + rhs.0;
+
+        let overflow = unsafe {
+            simd_and(
+                simd_xor(self.0.rows[7], res.rows[7]),
+                simd_xor(rhs.0.rows[7], res.rows[7]),
+            )
+        };
+
+        self.saturate(res, overflow)
+    }
+
+    /*
+     * Subtracts two i8x512s, clamping the lanes that overflow to i8::MIN or i8::MAX, like
+     * i8::saturating_sub. A lane overflows when its operands have different signs and the
+     * sign of the difference differs from the one of self.
+     */
+    pub fn saturating_sub(&self, rhs: &i8x512) -> i8x512 {
+        let res = self.0 - rhs.0;
+
+        let overflow = unsafe {
+            simd_and(
+                simd_xor(self.0.rows[7], rhs.0.rows[7]),
+                simd_xor(self.0.rows[7], res.rows[7]),
+            )
+        };
+
+        self.saturate(res, overflow)
+    }
+}
+
+//#################################################################################################
+//
+//                                          on slices
+//
+//#################################################################################################
+
+/*
+ * Adds two slices of u8s lane by lane into out, with wrapping additions. The slices are processed
+ * in chunks of 512 u8s, each converted to a u8x512 and added the super-simd way, the last chunk
+ * of fewer than 512 u8s being added one u8 after the other. Panics if a, b and out don't all have
+ * the same length, like copy_from_slice.
+ */
+pub fn add_slices(a: &[u8], b: &[u8], out: &mut [u8]) {
+    assert!(
+        a.len() == b.len() && a.len() == out.len(),
+        "add_slices: slices of different lengths ({}, {} and {})",
+        a.len(), b.len(), out.len(),
+    );
+
+    let mut a_chunks = a.chunks_exact(512);
+    let mut b_chunks = b.chunks_exact(512);
+    let mut out_chunks = out.chunks_exact_mut(512);
+
+    for ((a, b), out) in (&mut a_chunks).zip(&mut b_chunks).zip(&mut out_chunks) {
+        let a = u8x512::from(<&[u8; 512]>::try_from(a).unwrap());
+        let b = u8x512::from(<&[u8; 512]>::try_from(b).unwrap());
+        (a + b).write_to(<&mut [u8; 512]>::try_from(out).unwrap());
+    }
+
+    let a_rem = a_chunks.remainder();
+    let b_rem = b_chunks.remainder();
+
+    for (i, out) in out_chunks.into_remainder().iter_mut().enumerate() {
+        *out = a_rem[i].wrapping_add(b_rem[i]);
+    }
+}
+
+/*
+ * Adds two slices of u8s lane by lane into out like add_slices, the slices being split in blocks
+ * of 64 chunks of 512 u8s, added in parallel on the threads of rayon. Each block is written to
+ * its own part of out, so the result is exactly the one of add_slices. Panics if a, b and out
+ * don't all have the same length. Needs the rayon feature.
+ */
+#[cfg(feature = "rayon")]
+pub fn par_add_slices(a: &[u8], b: &[u8], out: &mut [u8]) {
+    use rayon::prelude::*;
+
+    const BLOCK: usize = 64 * 512;
+
+    assert!(
+        a.len() == b.len() && a.len() == out.len(),
+        "par_add_slices: slices of different lengths ({}, {} and {})",
+        a.len(), b.len(), out.len(),
+    );
+
+    out.par_chunks_mut(BLOCK)
+        .zip(a.par_chunks(BLOCK))
+        .zip(b.par_chunks(BLOCK))
+        .for_each(|((out, a), b)| add_slices(a, b, out));
+}
+
+/*
+ * Splits a slice of u8s in chunks of 512 u8s, each converted to a u8x512 with
+ * from_slice_padded. The last chunk, if it has fewer than 512 u8s, is padded with zeros, so
+ * that its u8x512 has lanes set to 0 past the end of data. An empty slice yields no u8x512 at
+ * all.
+ */
+pub fn chunks_bitsliced(data: &[u8]) -> impl Iterator<Item = u8x512> + '_ {
+    data.chunks(512).map(u8x512::from_slice_padded)
+}
+
+/*
+ * The inverse of chunks_bitsliced: concatenates the lanes of the u8x512s into a Vec of len u8s,
+ * dropping the padding of the last chunk. The u8x512s past the first len lanes are ignored, and
+ * panics if there are fewer than len lanes. Needs the alloc feature.
+ */
+#[cfg(feature = "alloc")]
+pub fn collect_bitsliced<I: IntoIterator<Item = u8x512>>(chunks: I, len: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(len);
+    let mut chunks = chunks.into_iter();
+
+    while data.len() < len {
+        let chunk = chunks.next().expect("collect_bitsliced: fewer lanes than len");
+        let cols = <[u8; 512]>::from(&chunk);
+        let n = (len - data.len()).min(512);
+        data.extend_from_slice(&cols[..n]);
+    }
+
+    data
+}
+
+//#################################################################################################
+//
+//                                         mod tests
+//
+//#################################################################################################
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    extern crate test;
+    use test::Bencher;
+
+    use std::collections::HashSet;
+    use std::collections::hash_map::DefaultHasher;
+    use std::convert::TryFrom;
+    use std::hash::{Hash, Hasher};
+    use std::mem::{MaybeUninit, transmute};
+
+    use super::{Bitsliced, ConversionError, Lane, Layout, Row, Shape};
+    use super::{add_slices, chunks_bitsliced, collect_bitsliced};
+    #[cfg(feature = "rayon")]
+    use super::par_add_slices;
+    use super::{i8x512, u8x256, u8x512, u8x1024, u16x512, u32x512, u64x8};
+
+    #[cfg(feature = "portable-simd")]
+    use super::{simd_and, simd_or, simd_xor};
+
+    /*
+     * The seed used to fill the arrays before the benchmarks.s
+     */
+    const SEED: u32 = 123;
+
+    /*
+     * Generates pseudo-random 32 bits integers with the xorshift32 algorithm.
+     */
+    fn xorshift32(state: &mut u32) -> u32 {
+        let mut x = *state;
+        x ^= x.wrapping_shl(13);
+        x ^= x.wrapping_shr(17);
+        x ^= x.wrapping_shl(5);
+        *state = x;
+        x
+    }
+
+    /*
+     * Fills an array of 512 u8s with pseudo-random integers.
+     */
+    fn init_array(state: &mut u32) -> [u8; 512] {
+        let mut res = [0; 512];
+
+        for x in res.iter_mut() {
+            *x = (xorshift32(state) & 0xFF) as u8;
+        }
+
+        res
+    }
+
+    /*
+     * Fills an array of 512 u32s with pseudo-random integers.
+     */
+    fn init_array_u32(state: &mut u32) -> [u32; 512] {
+        let mut res = [0; 512];
+
+        for x in res.iter_mut() {
+            *x = xorshift32(state);
+        }
+
+        res
+    }
+
+    /*
+     * Fills an array of 512 u16s with pseudo-random integers.
+     */
+    fn init_array_u16(state: &mut u32) -> [u16; 512] {
+        let mut res = [0; 512];
+
+        for x in res.iter_mut() {
+            *x = (xorshift32(state) & 0xFFFF) as u16;
+        }
+
+        res
+    }
+
+    /*
+     * Fills an array of 512 i8s with pseudo-random integers.
+     */
+    fn init_array_i8(state: &mut u32) -> [i8; 512] {
+        let mut res = [0; 512];
+
+        for x in res.iter_mut() {
+            *x = xorshift32(state) as i8;
+        }
+
+        res
+    }
+
+    /*
+     * The horizontalization done before the bit-matrix transposition, looping over the bits
+     * set in each lane. Kept as a reference for the tests and the benchmarks.
+     */
+    fn horizontalize_reference<const BITS: usize, const LANES: usize>(
+        cols: &[<Shape<BITS, LANES> as Layout>::Lane; LANES],
+    ) -> Bitsliced<BITS, LANES>
+    where
+        Shape<BITS, LANES>: Layout,
+    {
+        let mut rows = [<Shape<BITS, LANES> as Layout>::Row::ZERO; BITS];
+
+        for (i, &col) in cols.iter().enumerate() {
+            let mut col: u64 = col.into();
+            let h_mask = 1 << (i % 64);
+
+            for _ in 0..col.count_ones() {
+                let v = col.trailing_zeros();
+                col ^= 1 << v;
+                *rows[v as usize].word_mut(i / 64) |= h_mask;
+            }
+        }
+
+        Bitsliced {rows}
+    }
+
+    /*
+     * The verticalization done before the inverse bit-matrix transposition, looping over the
+     * bits set in each word of the rows. Kept as a reference for the tests and the benchmarks.
+     */
+    fn verticalize_reference<const BITS: usize, const LANES: usize>(
+        a: &Bitsliced<BITS, LANES>,
+    ) -> [<Shape<BITS, LANES> as Layout>::Lane; LANES]
+    where
+        Shape<BITS, LANES>: Layout,
+    {
+        let mut out = [0u64; LANES];
+
+        for (v, row) in a.rows.iter().enumerate() {
+            for w in 0..LANES / 64 {
+                let mut row_cpy = row.word(w);
+
+                for _ in 0..row_cpy.count_ones() {
+                    let h = row_cpy.trailing_zeros();
+                    row_cpy ^= 1 << h;
+                    out[h as usize + w * 64] |= 1 << v;
+                }
+            }
+        }
+
+        out.map(<Shape<BITS, LANES> as Layout>::Lane::truncate)
+    }
+
+    /*
+     * Asserts that two Bitsliced have exactly the same rows.
+     */
+    fn assert_same_rows<const BITS: usize, const LANES: usize>(
+        a: &Bitsliced<BITS, LANES>,
+        b: &Bitsliced<BITS, LANES>,
+    )
+    where
+        Shape<BITS, LANES>: Layout,
+    {
+        for v in 0..BITS {
+            for w in 0..LANES / 64 {
+                assert_eq!(a.rows[v].word(w), b.rows[v].word(w));
+            }
+        }
+    }
+
+    /*
+     * Tests the correctness of the addition of two u8x512s. Results are compared with
+     * cpu's additions.
+     */
+    #[test]
+    fn add() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        let res_ssimd = a1_ssimd + a2_ssimd;
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the correctness of the addition assignement of two u8x512s. Results are compared
+     * with cpu's additions.
+     */
+    #[test]
+    fn add_assign() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let mut a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        a1_ssimd += a2_ssimd;
+
+        let res: Box<[u8; 512]> = (&a1_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the correctness of the subtraction of two u8x512s. Results are compared with
+     * cpu's subtractions.
+     */
+    #[test]
+    fn sub() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        let res_ssimd = a1_ssimd - a2_ssimd;
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_sub(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the addition and the subtraction, which build their rows without MaybeUninit, on
+     * every pair of u8s, against cpu's wrapping operations and their assignment versions.
+     */
+    #[test]
+    fn add_sub_every_pair() {
+        for k in 0..128 {
+            let mut a = [0; 512];
+            let mut b = [0; 512];
+
+            for i in 0..512 {
+                a[i] = (2 * k + i / 256) as u8;
+                b[i] = i as u8;
+            }
+
+            let a_ssimd = u8x512::from(&a);
+            let b_ssimd = u8x512::from(&b);
+
+            let sum = <[u8; 512]>::from(&(a_ssimd + b_ssimd));
+            let diff = <[u8; 512]>::from(&(a_ssimd - b_ssimd));
+
+            for i in 0..512 {
+                assert_eq!(sum[i], a[i].wrapping_add(b[i]));
+                assert_eq!(diff[i], a[i].wrapping_sub(b[i]));
+            }
+
+            let mut sum_assign = a_ssimd;
+            sum_assign += b_ssimd;
+            let mut diff_assign = a_ssimd;
+            diff_assign -= b_ssimd;
+
+            assert_eq!(sum_assign, a_ssimd + b_ssimd);
+            assert_eq!(diff_assign, a_ssimd - b_ssimd);
+        }
+    }
+
+    /*
+     * Tests the correctness of the subtraction assignement of two u8x512s. Results are compared
+     * with cpu's subtractions, then the subtrahend is added back to get the original vector.
+     */
+    #[test]
+    fn sub_assign() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let mut a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        a1_ssimd -= a2_ssimd;
+
+        let res: Box<[u8; 512]> = (&a1_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_sub(a2[i]));
+        }
+
+        a1_ssimd += a2_ssimd;
+
+        let res: Box<[u8; 512]> = (&a1_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i]);
+        }
+    }
+
+    /*
+     * Tests the correctness of the multiplication of two u8x512s. Results are compared with
+     * cpu's multiplications.
+     */
+    #[test]
+    fn mul() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        let res_ssimd = a1_ssimd * a2_ssimd;
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_mul(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the correctness of the multiplication assignement of two u8x512s. Results are
+     * compared with cpu's multiplications.
+     */
+    #[test]
+    fn mul_assign() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let mut a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        a1_ssimd *= a2_ssimd;
+
+        let res: Box<[u8; 512]> = (&a1_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_mul(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the correctness of the negation of a u8x512. Results are compared with cpu's
+     * negations, and adding a vector to its negation must give zero in every lane.
+     */
+    #[test]
+    fn neg() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let a_ssimd = u8x512::from(&a);
+
+        let res: Box<[u8; 512]> = (&-a_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a[i].wrapping_neg());
+        }
+
+        let res: Box<[u8; 512]> = (&(a_ssimd + -a_ssimd)).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], 0);
+        }
+    }
+
+    /*
+     * Tests the carry out of the addition of two u8x512s. Every third lane is set to overflow
+     * (0xFF + 0x01), and the mask must match the lanes whose cpu's addition overflows.
+     */
+    #[test]
+    fn carrying_add() {
+        let mut state = SEED;
+        let mut a1 = init_array(&mut state);
+        let mut a2 = init_array(&mut state);
+
+        for i in (0..512).step_by(3) {
+            a1[i] = 0xFF;
+            a2[i] = 0x01;
+        }
+
+        let (res_ssimd, carry) = u8x512::from(&a1).carrying_add(&u8x512::from(&a2));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+        let carry = carry.to_bitmask();
+
+        for i in 0..512 {
+            let (sum, overflow) = a1[i].overflowing_add(a2[i]);
+            assert_eq!(res[i], sum);
+            assert_eq!((carry[i / 64] >> (i % 64)) & 1 == 1, overflow);
+
+            if i % 3 == 0 {
+                assert!(overflow);
+            }
+        }
+    }
+
+    /*
+     * Tests the correctness of the saturating addition of two u8x512s. Every fourth lane of the
+     * first operand is set to 0xFF. Results are compared with cpu's saturating additions.
+     */
+    #[test]
+    fn saturating_add() {
+        let mut state = SEED;
+        let mut a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        for i in (0..512).step_by(4) {
+            a1[i] = 0xFF;
+        }
+
+        let res_ssimd = u8x512::from(&a1).saturating_add(&u8x512::from(&a2));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].saturating_add(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the borrow out of the subtraction of two u8x512s. Results and mask are compared
+     * with cpu's overflowing subtractions.
+     */
+    #[test]
+    fn borrowing_sub() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let (res_ssimd, borrow) = u8x512::from(&a1).borrowing_sub(&u8x512::from(&a2));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+        let borrow = borrow.to_bitmask();
+
+        for i in 0..512 {
+            let (diff, overflow) = a1[i].overflowing_sub(a2[i]);
+            assert_eq!(res[i], diff);
+            assert_eq!((borrow[i / 64] >> (i % 64)) & 1 == 1, overflow);
+        }
+    }
+
+    /*
+     * Tests the correctness of the saturating subtraction of two u8x512s. Every fourth lane is
+     * set to 0x00 - 0x01. Results are compared with cpu's saturating subtractions.
+     */
+    #[test]
+    fn saturating_sub() {
+        let mut state = SEED;
+        let mut a1 = init_array(&mut state);
+        let mut a2 = init_array(&mut state);
+
+        for i in (0..512).step_by(4) {
+            a1[i] = 0x00;
+            a2[i] = 0x01;
+        }
+
+        let res_ssimd = u8x512::from(&a1).saturating_sub(&u8x512::from(&a2));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].saturating_sub(a2[i]));
+
+            if i % 4 == 0 {
+                assert_eq!(res[i], 0);
+            }
+        }
+    }
+
+    /*
+     * Tests the overflow mask of checked_add against a known pattern: every fifth lane is set to
+     * overflow (0x80 + 0x80) and all the others are small enough not to.
+     */
+    #[test]
+    fn checked_add() {
+        let mut state = SEED;
+        let mut a1 = init_array(&mut state);
+        let mut a2 = init_array(&mut state);
+
+        for i in 0..512 {
+            if i % 5 == 0 {
+                a1[i] = 0x80;
+                a2[i] = 0x80;
+            } else {
+                a1[i] &= 0x7F;
+                a2[i] &= 0x7F;
+            }
+        }
+
+        let (res_ssimd, overflow) = u8x512::from(&a1).checked_add(&u8x512::from(&a2));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+        let bits = overflow.to_bitmask();
+
+        assert!(overflow.any());
+        assert_eq!(overflow.count(), 103);
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+            assert_eq!((bits[i / 64] >> (i % 64)) & 1 == 1, i % 5 == 0);
+        }
+
+        let (_, overflow) = u8x512::from(&a2).checked_add(&u8x512::from(&[0; 512]));
+
+        assert!(!overflow.any());
+        assert_eq!(overflow.count(), 0);
+    }
+
+    /*
+     * Tests the correctness of the division of two u8x512s. Results are compared with cpu's
+     * divisions, the divisors being forced nonzero, then lanes divided by zero must give 0xFF.
+     */
+    #[test]
+    fn div_lanes() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let mut a2 = init_array(&mut state);
+
+        for x in a2.iter_mut() {
+            *x = (*x).max(1);
+        }
+
+        let res_ssimd = u8x512::from(&a1).div_lanes(&u8x512::from(&a2));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i] / a2[i]);
+        }
+
+        let res_ssimd = u8x512::from(&a1).div_lanes(&u8x512::from(&[0; 512]));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], 0xFF);
+        }
+    }
+
+    /*
+     * Tests the correctness of the remainder of two u8x512s. Results are compared with cpu's
+     * remainders, the divisors being forced nonzero, and a == (a / b) * b + (a % b) must hold.
+     * Lanes divided by zero must be left unchanged.
+     */
+    #[test]
+    fn rem_lanes() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let mut a2 = init_array(&mut state);
+
+        for x in a2.iter_mut() {
+            *x = (*x).max(1);
+        }
+
+        let a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        let res_ssimd = a1_ssimd.rem_lanes(&a2_ssimd);
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i] % a2[i]);
+        }
+
+        let res_ssimd = a1_ssimd.div_lanes(&a2_ssimd) * a2_ssimd + res_ssimd;
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i]);
+        }
+
+        let res_ssimd = a1_ssimd.rem_lanes(&u8x512::from(&[0; 512]));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i]);
+        }
+    }
+
+    /*
+     * Tests the correctness of the fused multiply-add of three u8x512s. Results are compared
+     * with cpu's multiplications followed by additions.
+     */
+    #[test]
+    fn mul_add() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+        let a3 = init_array(&mut state);
+
+        let res_ssimd = u8x512::from(&a1).mul_add(&u8x512::from(&a2), &u8x512::from(&a3));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_mul(a2[i]).wrapping_add(a3[i]));
+        }
+    }
+
+    /*
+     * Tests the horizontal sum of a u8x512 against the sum of the source array.
+     */
+    #[test]
+    fn reduce_sum() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let sum: u32 = a.iter().map(|&x| x as u32).sum();
+
+        assert_eq!(u8x512::from(&a).reduce_sum(), sum);
+        assert_eq!(u8x512::from(&[0xFF; 512]).reduce_sum(), 0xFF * 512);
+    }
+
+    /*
+     * Tests that a splatted u8x512 converts back to 512 copies of the same byte.
+     */
+    #[test]
+    fn splat() {
+        let res: Box<[u8; 512]> = (&u8x512::splat(0xAB)).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], 0xAB);
+        }
+    }
+
+    /*
+     * Tests the increment of a u8x512 against the addition of a splatted one, then checks that
+     * 0xFF lanes wrap around to 0.
+     */
+    #[test]
+    fn increment() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let res: Box<[u8; 512]> = (&u8x512::from(&a).increment()).into();
+        let expected: Box<[u8; 512]> = (&(u8x512::from(&a) + u8x512::splat(1))).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a[i].wrapping_add(1));
+            assert_eq!(res[i], expected[i]);
+        }
+
+        let mut a_ssimd = u8x512::splat(0xFF);
+        a_ssimd.increment_assign();
+
+        let res: Box<[u8; 512]> = (&a_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], 0);
+        }
+    }
+
+    /*
+     * Tests the correctness of the rounded average of two u8x512s. Results are compared with
+     * the same formula computed on u16s.
+     */
+    #[test]
+    fn avg() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let res_ssimd = u8x512::from(&a1).avg(&u8x512::from(&a2));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i] as u16, (a1[i] as u16 + a2[i] as u16 + 1) >> 1);
+        }
+    }
+
+    /*
+     * Tests the correctness of the absolute difference of two u8x512s. Results are compared
+     * with cpu's absolute differences.
+     */
+    #[test]
+    fn abs_diff() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let res_ssimd = u8x512::from(&a1).abs_diff(&u8x512::from(&a2));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].abs_diff(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the correctness of the modular addition of two u8x512s with several moduli, powers
+     * of two or not. Results are compared with the same formula computed on u16s.
+     */
+    #[test]
+    fn add_mod() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        for &modulus in [1, 2, 3, 7, 100, 128, 129, 251, 255].iter() {
+            let res: Box<[u8; 512]> = (&a1_ssimd.add_mod(&a2_ssimd, modulus)).into();
+
+            for i in 0..512 {
+                assert_eq!(res[i] as u16, (a1[i] as u16 + a2[i] as u16) % modulus as u16);
+            }
+        }
+    }
+
+    /*
+     * Tests that the carry-lookahead addition of two u8x512s gives the same results as the
+     * ripple-carry one.
+     */
+    #[test]
+    fn add_fast() {
+        let mut state = SEED;
+
+        for _ in 0..16 {
+            let a1_ssimd = u8x512::from(&init_array(&mut state));
+            let a2_ssimd = u8x512::from(&init_array(&mut state));
+
+            let res: Box<[u8; 512]> = (&a1_ssimd.add_fast(&a2_ssimd)).into();
+            let expected: Box<[u8; 512]> = (&(a1_ssimd + a2_ssimd)).into();
+
+            assert_eq!(res.to_vec(), expected.to_vec());
+        }
+    }
+
+    /*
+     * Tests the correctness of the xor of two u8x512s, and of the xor assignement. Results are
+     * compared with cpu's xors.
+     */
+    #[test]
+    fn bitxor() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let mut a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        let res: Box<[u8; 512]> = (&(a1_ssimd ^ a2_ssimd)).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i] ^ a2[i]);
+        }
+
+        a1_ssimd ^= a2_ssimd;
+
+        let res: Box<[u8; 512]> = (&a1_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i] ^ a2[i]);
+        }
+    }
+
+    /*
+     * Tests the correctness of the and and the or of two u8x512s, and of their assignements.
+     * Results are compared with cpu's ands and ors.
+     */
+    #[test]
+    fn bitand_bitor() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        let and: Box<[u8; 512]> = (&(a1_ssimd & a2_ssimd)).into();
+        let or: Box<[u8; 512]> = (&(a1_ssimd | a2_ssimd)).into();
+
+        for i in 0..512 {
+            assert_eq!(and[i], a1[i] & a2[i]);
+            assert_eq!(or[i], a1[i] | a2[i]);
+        }
+
+        let mut and_ssimd = a1_ssimd;
+        and_ssimd &= a2_ssimd;
+        let mut or_ssimd = a1_ssimd;
+        or_ssimd |= a2_ssimd;
+
+        let and: Box<[u8; 512]> = (&and_ssimd).into();
+        let or: Box<[u8; 512]> = (&or_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(and[i], a1[i] & a2[i]);
+            assert_eq!(or[i], a1[i] | a2[i]);
+        }
+    }
+
+    /*
+     * Tests the correctness of the bitwise not of a u8x512. Results are compared with cpu's
+     * nots, and inverting twice must give back the original vector.
+     */
+    #[test]
+    fn not() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let a_ssimd = u8x512::from(&a);
+
+        let res: Box<[u8; 512]> = (&!a_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], !a[i]);
+        }
+
+        let res: Box<[u8; 512]> = (&!!a_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a[i]);
+        }
+    }
+
+    /*
+     * Tests the correctness of the left shift of a u8x512 for several shift amounts, including
+     * one that shifts every bit out. Results are compared with cpu's shifts.
+     */
+    #[test]
+    fn shl_lanes() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let a_ssimd = u8x512::from(&a);
+
+        for &n in [0, 1, 4, 7, 8].iter() {
+            let res: Box<[u8; 512]> = (&a_ssimd.shl_lanes(n)).into();
+
+            for i in 0..512 {
+                assert_eq!(res[i], a[i].checked_shl(n).unwrap_or(0));
+            }
+        }
+    }
+
+    /*
+     * Tests the correctness of the right shift of a u8x512 for every shift amount up to 9.
+     * Results are compared with cpu's shifts.
+     */
+    #[test]
+    fn shr_lanes() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let a_ssimd = u8x512::from(&a);
+
+        for n in 0..10 {
+            let res: Box<[u8; 512]> = (&a_ssimd.shr_lanes(n)).into();
+
+            for i in 0..512 {
+                assert_eq!(res[i], a[i].checked_shr(n).unwrap_or(0));
+            }
+        }
+    }
+
+    /*
+     * Tests the correctness of the bit rotations of a u8x512 for every rotation amount. Results
+     * are compared with cpu's rotations.
+     */
+    #[test]
+    fn rotate() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let a_ssimd = u8x512::from(&a);
+
+        for n in 0..8 {
+            let left: Box<[u8; 512]> = (&a_ssimd.rotate_left(n)).into();
+            let right: Box<[u8; 512]> = (&a_ssimd.rotate_right(n)).into();
+
+            for i in 0..512 {
+                assert_eq!(left[i], a[i].rotate_left(n));
+                assert_eq!(right[i], a[i].rotate_right(n));
+            }
+        }
+    }
+
+    /*
+     * Tests the correctness of the arithmetic right shift of a u8x512 for every shift amount up
+     * to 9, on both negative and positive lanes. Results are compared with cpu's i8 shifts.
+     */
+    #[test]
+    fn shr_arithmetic() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        assert!(a.iter().any(|&x| (x as i8) < 0));
+        assert!(a.iter().any(|&x| (x as i8) > 0));
+
+        let a_ssimd = u8x512::from(&a);
+
+        for n in 0..10 {
+            let res: Box<[u8; 512]> = (&a_ssimd.shr_arithmetic(n)).into();
+
+            for i in 0..512 {
+                assert_eq!(res[i], (a[i] as i8 >> n.min(7)) as u8);
+            }
+        }
+    }
+
+    /*
+     * Tests the correctness of the bit reversal of a u8x512. Results are compared with cpu's
+     * bit reversals.
+     */
+    #[test]
+    fn reverse_bits() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let res: Box<[u8; 512]> = (&u8x512::from(&a).reverse_bits()).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a[i].reverse_bits());
+        }
+    }
+
+    /*
+     * Tests the correctness of the and-not of two u8x512s. Results are compared with cpu's
+     * and-nots.
+     */
+    #[test]
+    fn and_not() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let res_ssimd = u8x512::from(&a1).and_not(&u8x512::from(&a2));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i] & !a2[i]);
+        }
+    }
+
+    /*
+     * Tests the bitwise blend of two u8x512s, first on known bit patterns, then on random data
+     * compared with cpu's blends.
+     */
+    #[test]
+    fn select_bits() {
+        let mask = u8x512::splat(0xF0);
+        let res_ssimd = u8x512::splat(0xAA).select_bits(&u8x512::splat(0x55), &mask);
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], 0xA5);
+        }
+
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+        let mask = init_array(&mut state);
+
+        let res_ssimd = u8x512::from(&a1).select_bits(&u8x512::from(&a2), &u8x512::from(&mask));
+        let res: Box<[u8; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], (a1[i] & mask[i]) | (a2[i] & !mask[i]));
+        }
+    }
+
+    /*
+     * Tests the correctness of the population count of a u8x512. Results are compared with
+     * cpu's population counts.
+     */
+    #[test]
+    fn count_ones() {
+        let mut state = SEED;
+        let mut a = init_array(&mut state);
+        a[0] = 0xFF;
+        a[1] = 0x00;
+
+        let res: Box<[u8; 512]> = (&u8x512::from(&a).count_ones()).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i] as u32, a[i].count_ones());
+        }
+    }
+
+    /*
+     * Tests the correctness of the leading zeros count of a u8x512, including all-zero lanes.
+     * Results are compared with cpu's leading zeros counts.
+     */
+    #[test]
+    fn leading_zeros() {
+        let mut state = SEED;
+        let mut a = init_array(&mut state);
+
+        for (i, x) in a.iter_mut().enumerate() {
+            *x = x.checked_shr(i as u32 % 9).unwrap_or(0);
+        }
+
+        let res: Box<[u8; 512]> = (&u8x512::from(&a).leading_zeros()).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i] as u32, a[i].leading_zeros());
+        }
+    }
+
+    /*
+     * Tests the correctness of the trailing zeros count of a u8x512, including all-zero lanes.
+     * Results are compared with cpu's trailing zeros counts.
+     */
+    #[test]
+    fn trailing_zeros() {
+        let mut state = SEED;
+        let mut a = init_array(&mut state);
+
+        for (i, x) in a.iter_mut().enumerate() {
+            *x = x.checked_shl(i as u32 % 9).unwrap_or(0);
+        }
+
+        let res: Box<[u8; 512]> = (&u8x512::from(&a).trailing_zeros()).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i] as u32, a[i].trailing_zeros());
+        }
+    }
+
+    /*
+     * Tests the correctness of the parity of a u8x512. Results are compared with the parity of
+     * cpu's population counts.
+     */
+    #[test]
+    fn parity() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let parity = u8x512::from(&a).parity().to_bitmask();
+
+        for i in 0..512 {
+            assert_eq!((parity[i / 64] >> (i % 64)) & 1, (a[i].count_ones() & 1) as u64);
+        }
+    }
+
+    /*
+     * Tests the equality mask of two u8x512s. One lane out of three of the second operand is a
+     * copy of the first one, and the mask must be set exactly in the lanes that are equal.
+     */
+    #[test]
+    fn simd_eq() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let mut a2 = init_array(&mut state);
+
+        for i in (0..512).step_by(3) {
+            a2[i] = a1[i];
+        }
+
+        let eq = u8x512::from(&a1).simd_eq(&u8x512::from(&a2)).to_bitmask();
+
+        for i in 0..512 {
+            assert_eq!((eq[i / 64] >> (i % 64)) & 1 == 1, a1[i] == a2[i]);
+        }
+    }
+
+    /*
+     * Tests the comparison masks of two u8x512s. One lane out of three of the second operand is
+     * a copy of the first one so that the equal case is covered. Results are compared with cpu's
+     * comparisons.
+     */
+    #[test]
+    fn compare() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let mut a2 = init_array(&mut state);
+
+        for i in (0..512).step_by(3) {
+            a2[i] = a1[i];
+        }
+
+        let a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        let lt = a1_ssimd.simd_lt(&a2_ssimd).to_bitmask();
+        let gt = a1_ssimd.simd_gt(&a2_ssimd).to_bitmask();
+        let le = a1_ssimd.simd_le(&a2_ssimd).to_bitmask();
+        let ge = a1_ssimd.simd_ge(&a2_ssimd).to_bitmask();
+
+        for i in 0..512 {
+            assert_eq!((lt[i / 64] >> (i % 64)) & 1 == 1, a1[i] < a2[i]);
+            assert_eq!((gt[i / 64] >> (i % 64)) & 1 == 1, a1[i] > a2[i]);
+            assert_eq!((le[i / 64] >> (i % 64)) & 1 == 1, a1[i] <= a2[i]);
+            assert_eq!((ge[i / 64] >> (i % 64)) & 1 == 1, a1[i] >= a2[i]);
+        }
+    }
+
+    /*
+     * Tests the lane-wise minimum and maximum of two u8x512s. Results are compared with cpu's
+     * minimums and maximums.
+     */
+    #[test]
+    fn simd_min_max() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        let min: Box<[u8; 512]> = (&a1_ssimd.simd_min(&a2_ssimd)).into();
+        let max: Box<[u8; 512]> = (&a1_ssimd.simd_max(&a2_ssimd)).into();
+
+        for i in 0..512 {
+            assert_eq!(min[i], std::cmp::min(a1[i], a2[i]));
+            assert_eq!(max[i], std::cmp::max(a1[i], a2[i]));
+        }
+    }
+
+    /*
+     * Tests the clamping of a u8x512, with some lanes sitting right on the bounds. Results are
+     * compared with cpu's clamps.
+     */
+    #[test]
+    fn simd_clamp() {
+        let mut state = SEED;
+        let mut a = init_array(&mut state);
+        a[0] = 50;
+        a[1] = 200;
+        a[2] = 49;
+        a[3] = 201;
+
+        let res: Box<[u8; 512]> = (&u8x512::from(&a).simd_clamp(50, 200)).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a[i].clamp(50, 200));
+        }
+
+        let res: Box<[u8; 512]> = (&u8x512::from(&a).simd_clamp(7, 7)).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], 7);
+        }
+    }
+
+    /*
+     * Tests the equality of u8x512s, with equal vectors and vectors differing in a single bit
+     * of a single lane.
+     */
+    #[test]
+    fn partial_eq() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        assert_eq!(u8x512::from(&a1), u8x512::from(&a1));
+        assert_ne!(u8x512::from(&a1), u8x512::from(&a2));
+
+        for &(lane, bit) in [(0, 0), (63, 7), (64, 3), (511, 7)].iter() {
+            let mut a3 = a1;
+            a3[lane] ^= 1 << bit;
+
+            assert_ne!(u8x512::from(&a1), u8x512::from(&a3));
+        }
+    }
+
+    /*
+     * Tests the queries of Mask512 on empty, full and partial masks, then blends two u8x512s
+     * with a mask and checks that blending the other way around gives back the other lanes.
+     */
+    #[test]
+    fn mask512() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let mut a2 = init_array(&mut state);
+
+        let a1_ssimd = u8x512::from(&a1);
+
+        let none = a1_ssimd.simd_lt(&a1_ssimd);
+        let full = a1_ssimd.simd_eq(&a1_ssimd);
+
+        assert!(!none.any() && !none.all() && none.count() == 0);
+        assert!(full.any() && full.all() && full.count() == 512);
+
+        for i in (0..512).step_by(2) {
+            a2[i] = a1[i];
+        }
+
+        let a2_ssimd = u8x512::from(&a2);
+        let mask = a1_ssimd.simd_eq(&a2_ssimd);
+
+        assert!(mask.any() && !mask.all());
+        assert_eq!(mask.count(), (0..512).filter(|&i| a1[i] == a2[i]).count() as u32);
+
+        let ones = u8x512::splat(0xFF);
+        let zeros = u8x512::splat(0x00);
+
+        let res: Box<[u8; 512]> = (&mask.select(&ones, &zeros)).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], if a1[i] == a2[i] { 0xFF } else { 0x00 });
+        }
+
+        let a3 = init_array(&mut state);
+        let a3_ssimd = u8x512::from(&a3);
+
+        let blend = mask.select(&a1_ssimd, &a3_ssimd);
+        let back = mask.select(&a3_ssimd, &blend);
+
+        assert_eq!(back, a3_ssimd);
+    }
+
+    /*
+     * Tests that find returns the index of the first occurence of a byte, or None when there
+     * is none.
+     */
+    #[test]
+    fn find() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let a_ssimd = u8x512::from(&a);
+
+        for &needle in a.iter().take(32) {
+            assert_eq!(a_ssimd.find(needle), a.iter().position(|&x| x == needle));
+        }
+
+        let mut a = [0; 512];
+        a[300] = 1;
+        a[400] = 1;
+
+        assert_eq!(u8x512::from(&a).find(1), Some(300));
+        assert_eq!(u8x512::from(&a).find(2), None);
+    }
+
+    /*
+     * Tests the number of lanes of a u8x512 equal to a byte, for every possible byte. Results
+     * are compared with counts on the source array.
+     */
+    #[test]
+    fn count_eq() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let a_ssimd = u8x512::from(&a);
+
+        for value in 0..=255 {
+            assert_eq!(a_ssimd.count_eq(value), a.iter().filter(|&&x| x == value).count() as u32);
+        }
+    }
+
+    /*
+     * Tests that reading the lanes of a u8x512 one by one gives back the source array.
+     */
+    #[test]
+    fn get() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let a_ssimd = u8x512::from(&a);
+
+        for (i, &x) in a.iter().enumerate() {
+            assert_eq!(a_ssimd.get(i), x);
+        }
+    }
+
+    /*
+     * Tests that reading a lane out of bounds panics.
+     */
+    #[test]
+    #[should_panic]
+    fn get_out_of_bounds() {
+        u8x512::splat(0).get(512);
+    }
+
+    /*
+     * Tests that setting a few lanes of a u8x512 changes exactly those lanes.
+     */
+    #[test]
+    fn set() {
+        let mut state = SEED;
+        let mut a = init_array(&mut state);
+
+        let mut a_ssimd = u8x512::from(&a);
+
+        for &(index, value) in [(0, 0x00), (1, 0xFF), (63, 0xA5), (64, 0x5A), (511, 0x81)].iter() {
+            a_ssimd.set(index, value);
+            a[index] = value;
+        }
+
+        for (i, &x) in a.iter().enumerate() {
+            assert_eq!(a_ssimd.get(i), x);
+        }
+
+        assert_eq!(a_ssimd, u8x512::from(&a));
+    }
+
+    /*
+     * Tests that writing a lane out of bounds panics.
+     */
+    #[test]
+    #[should_panic]
+    fn set_out_of_bounds() {
+        u8x512::splat(0).set(512, 0);
+    }
+
+    /*
+     * Tests the conversion of slices to u8x512s, with a slice of the right length and slices
+     * that are too short or too long.
+     */
+    #[test]
+    fn try_from_slice() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+        let v = a.to_vec();
+
+        assert_eq!(u8x512::try_from(&v[..]), Ok(u8x512::from(&a)));
+        assert_eq!(
+            u8x512::try_from(&v[..511]),
+            Err(ConversionError::LengthMismatch {expected: 512, actual: 511}),
+        );
+
+        let mut v = v;
+        v.push(0);
+
+        assert_eq!(
+            u8x512::try_from(&v[..]),
+            Err(ConversionError::LengthMismatch {expected: 512, actual: 513}),
+        );
+    }
+
+    /*
+     * Tests that writing a u8x512 into a dirty buffer gives the same array as the boxed
+     * conversion.
+     */
+    #[test]
+    fn write_to() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let a_ssimd = u8x512::from(&a);
+
+        let mut out = [0xFF; 512];
+        a_ssimd.write_to(&mut out);
+
+        let boxed: Box<[u8; 512]> = (&a_ssimd).into();
+
+        assert_eq!(out.to_vec(), boxed.to_vec());
+        assert_eq!(out.to_vec(), a.to_vec());
+    }
+
+    /*
+     * Tests that the conversion to an array by value gives the same bytes as the boxed
+     * conversion.
+     */
+    #[test]
+    fn into_array_by_value() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let a_ssimd = u8x512::from(&a);
+
+        let array = <[u8; 512]>::from(&a_ssimd);
+        let boxed: Box<[u8; 512]> = (&a_ssimd).into();
+
+        assert_eq!(array.to_vec(), boxed.to_vec());
+        assert_eq!(array.to_vec(), a.to_vec());
+    }
+
+    /*
+     * Tests that iterating over a u8x512 gives back the source array.
+     */
+    #[test]
+    fn iter() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let lanes: Vec<u8> = u8x512::from(&a).iter().collect();
+
+        assert_eq!(lanes, a.to_vec());
+    }
+
+    /*
+     * Tests collecting an iterator of 512 u8s into a u8x512.
+     */
+    #[test]
+    fn from_iter() {
+        let a_ssimd: u8x512 = (0..512).map(|i| i as u8).collect();
+
+        for i in 0..512 {
+            assert_eq!(a_ssimd.get(i), i as u8);
+        }
+    }
+
+    /*
+     * Tests that collecting an iterator of fewer than 512 u8s panics.
+     */
+    #[test]
+    #[should_panic]
+    fn from_iter_too_short() {
+        let _: u8x512 = (0..511).map(|i| i as u8).collect();
+    }
+
+    /*
+     * Tests that collecting an iterator of more than 512 u8s panics.
+     */
+    #[test]
+    #[should_panic]
+    fn from_iter_too_long() {
+        let _: u8x512 = (0..513).map(|i| i as u8).collect();
+    }
+
+    /*
+     * Tests gathering the lanes of a u8x512 with a reversal permutation.
+     */
+    #[test]
+    fn gather() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let mut indices = [0; 512];
+
+        for (i, index) in indices.iter_mut().enumerate() {
+            *index = 511 - i;
+        }
+
+        let a_ssimd = u8x512::gather(&a, &indices);
+
+        for i in 0..512 {
+            assert_eq!(a_ssimd.get(i), a[511 - i]);
+        }
+    }
+
+    /*
+     * Tests that scattering a gathered u8x512 gives back the source array, for the identity
+     * permutation and a reversal.
+     */
+    #[test]
+    fn scatter() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let mut identity = [0; 512];
+        let mut reversal = [0; 512];
+
+        for i in 0..512 {
+            identity[i] = i;
+            reversal[i] = 511 - i;
+        }
+
+        for indices in [identity, reversal].iter() {
+            let mut out = [0; 512];
+            u8x512::gather(&a, indices).scatter(indices, &mut out);
+
+            assert_eq!(out.to_vec(), a.to_vec());
+        }
+    }
+
+    /*
+     * Tests that reversing the lanes of a u8x512 matches reversing the source array, and that
+     * reversing twice gives back the original vector.
+     */
+    #[test]
+    fn reverse() {
+        let mut state = SEED;
+        let mut a = init_array(&mut state);
+
+        let a_ssimd = u8x512::from(&a);
+        a.reverse();
+
+        assert_eq!(a_ssimd.reverse(), u8x512::from(&a));
+        assert_eq!(a_ssimd.reverse().reverse(), a_ssimd);
+    }
+
+    /*
+     * Tests the interleaving of two u8x512s against the lane mapping, then checks that
+     * deinterleaving gives back the original vectors.
+     */
+    #[test]
+    fn interleave() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        let (low, high) = a1_ssimd.interleave(&a2_ssimd);
+
+        for i in 0..256 {
+            assert_eq!(low.get(2 * i), a1[i]);
+            assert_eq!(low.get(2 * i + 1), a2[i]);
+            assert_eq!(high.get(2 * i), a1[256 + i]);
+            assert_eq!(high.get(2 * i + 1), a2[256 + i]);
+        }
+
+        assert_eq!(low.deinterleave(&high), (a1_ssimd, a2_ssimd));
+    }
+
+    /*
+     * Tests the ZERO and ONES constants, and the zero predicate on them and on vectors with a
+     * single lane set.
+     */
+    #[test]
+    fn zero_ones() {
+        assert_eq!(u8x512::ZERO, u8x512::from(&[0x00; 512]));
+        assert_eq!(u8x512::ONES, u8x512::from(&[0xFF; 512]));
+        assert_eq!(u8x512::ONES, u8x512::splat(0xFF));
+
+        assert!(u8x512::ZERO.is_zero());
+        assert!(!u8x512::ONES.is_zero());
+
+        for &lane in [0, 100, 511].iter() {
+            let mut a_ssimd = u8x512::ZERO;
+            a_ssimd.set(lane, 0x80);
+
+            assert!(!a_ssimd.is_zero());
+        }
+
+        let mut state = SEED;
+        let a_ssimd = u8x512::from(&init_array(&mut state));
+
+        assert!((a_ssimd - a_ssimd).is_zero());
+    }
+
+    /*
+     * Tests that building a u8x512 from a closure gives the same vector as converting an array
+     * built with the same closure.
+     */
+    #[test]
+    fn from_fn() {
+        let ramp = |i: usize| (i * 7 + 3) as u8;
+
+        let mut a = [0; 512];
+
+        for (i, x) in a.iter_mut().enumerate() {
+            *x = ramp(i);
+        }
+
+        assert_eq!(u8x512::from_fn(ramp), u8x512::from(&a));
+
+        let mut calls = Vec::new();
+        u8x512::from_fn(|i| { calls.push(i); 0 });
+
+        assert_eq!(calls, (0..512).collect::<Vec<_>>());
+    }
+
+    /*
+     * Tests that the planes of a u8x512 are its bits, and that putting them back one by one
+     * into another vector preserves it.
+     */
+    #[test]
+    fn plane() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let a_ssimd = u8x512::from(&a);
+        let mut b_ssimd = u8x512::ZERO;
+
+        for i in 0..8 {
+            b_ssimd.set_plane(i, a_ssimd.plane(i));
+        }
+
+        assert_eq!(b_ssimd, a_ssimd);
+
+        let mut c_ssimd = a_ssimd;
+        c_ssimd.set_plane(0, a_ssimd.plane(7));
+        c_ssimd.set_plane(7, a_ssimd.plane(0));
+
+        assert_eq!(c_ssimd, u8x512::from_fn(|i| {
+            (a[i] & 0x7E) | (a[i] >> 7) | (a[i] << 7)
+        }));
+    }
+
+    /*
+     * Tests that reading a plane out of bounds panics.
+     */
+    #[test]
+    #[should_panic]
+    fn plane_out_of_bounds() {
+        u8x512::ZERO.plane(8);
+    }
+
+    /*
+     * Tests that from_planes and into_planes round trip, and agree with plane, and that planes
+     * computed directly give the expected lanes.
+     */
+    #[test]
+    fn from_into_planes() {
+        let mut state = SEED;
+        let a = u8x512::from(&init_array(&mut state));
+
+        let planes = a.into_planes();
+
+        for (i, plane) in planes.iter().enumerate() {
+            assert_eq!(plane.to_array(), a.plane(i).to_array());
+        }
+
+        assert_eq!(u8x512::from_planes(planes), a);
+
+        let mut planes = [u64x8::ZERO; 8];
+        planes[0] = u64x8::splat(!0);
+        planes[7] = u64x8::splat(0xAAAAAAAAAAAAAAAA);
+
+        assert_eq!(u8x512::from_planes(planes), u8x512::from_fn(|i| {
+            if i % 2 == 0 { 0x01 } else { 0x81 }
+        }));
+    }
+
+    /*
+     * Tests the correctness of the addition of two u16x512s. Results are compared with cpu's
+     * additions.
+     */
+    #[test]
+    fn add_u16() {
+        let mut state = SEED;
+        let a1 = init_array_u16(&mut state);
+        let a2 = init_array_u16(&mut state);
+
+        let a1_ssimd = u16x512::from(&a1);
+        let a2_ssimd = u16x512::from(&a2);
+
+        let res_ssimd = a1_ssimd + a2_ssimd;
+        let res: Box<[u16; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the correctness of the addition assignement of two u16x512s. Results are compared
+     * with cpu's additions.
+     */
+    #[test]
+    fn add_assign_u16() {
+        let mut state = SEED;
+        let a1 = init_array_u16(&mut state);
+        let a2 = init_array_u16(&mut state);
+
+        let mut a1_ssimd = u16x512::from(&a1);
+        let a2_ssimd = u16x512::from(&a2);
+
+        a1_ssimd += a2_ssimd;
+
+        let res: Box<[u16; 512]> = (&a1_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the correctness of the addition of two u32x512s. Results are compared with cpu's
+     * additions.
+     */
+    #[test]
+    fn add_u32() {
+        let mut state = SEED;
+        let a1 = init_array_u32(&mut state);
+        let a2 = init_array_u32(&mut state);
+
+        let a1_ssimd = u32x512::from(&a1);
+        let a2_ssimd = u32x512::from(&a2);
+
+        let res_ssimd = a1_ssimd + a2_ssimd;
+        let res: Box<[u32; 512]> = (&res_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the correctness of the addition assignement of two u32x512s. Results are compared
+     * with cpu's additions.
+     */
+    #[test]
+    fn add_assign_u32() {
+        let mut state = SEED;
+        let a1 = init_array_u32(&mut state);
+        let a2 = init_array_u32(&mut state);
+
+        let mut a1_ssimd = u32x512::from(&a1);
+        let a2_ssimd = u32x512::from(&a2);
+
+        a1_ssimd += a2_ssimd;
+
+        let res: Box<[u32; 512]> = (&a1_ssimd).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the correctness of the addition and the addition assignement of two u8x256s.
+     * Results are compared with cpu's additions.
+     */
+    #[test]
+    fn add_u8x256() {
+        let mut state = SEED;
+        let mut a1 = [0; 256];
+        let mut a2 = [0; 256];
+        a1.copy_from_slice(&init_array(&mut state)[..256]);
+        a2.copy_from_slice(&init_array(&mut state)[..256]);
+
+        let mut a1_ssimd = u8x256::from(&a1);
+        let a2_ssimd = u8x256::from(&a2);
+
+        let res: Box<[u8; 256]> = (&(a1_ssimd + a2_ssimd)).into();
+
+        for i in 0..256 {
+            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+        }
+
+        a1_ssimd += a2_ssimd;
+
+        let res: Box<[u8; 256]> = (&a1_ssimd).into();
+
+        for i in 0..256 {
+            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the correctness of the addition and the addition assignement of two u8x1024s.
+     * Results are compared with cpu's additions.
+     */
+    #[test]
+    fn add_u8x1024() {
+        let mut state = SEED;
+        let mut a1 = [0; 1024];
+        let mut a2 = [0; 1024];
+        a1[..512].copy_from_slice(&init_array(&mut state));
+        a1[512..].copy_from_slice(&init_array(&mut state));
+        a2[..512].copy_from_slice(&init_array(&mut state));
+        a2[512..].copy_from_slice(&init_array(&mut state));
+
+        let mut a1_ssimd = u8x1024::from(&a1);
+        let a2_ssimd = u8x1024::from(&a2);
+
+        let res: Box<[u8; 1024]> = (&(a1_ssimd + a2_ssimd)).into();
+
+        for i in 0..1024 {
+            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+        }
+
+        a1_ssimd += a2_ssimd;
+
+        let res: Box<[u8; 1024]> = (&a1_ssimd).into();
+
+        for i in 0..1024 {
+            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the generic addition at two widths, naming the types through Bitsliced rather than
+     * through their aliases. Results are compared with cpu's additions.
+     */
+    #[test]
+    fn add_bitsliced() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+        let b1 = init_array_u16(&mut state);
+        let b2 = init_array_u16(&mut state);
+
+        let a = Bitsliced::<8, 512>::from(&a1) + Bitsliced::<8, 512>::from(&a2);
+        let b = Bitsliced::<16, 512>::from(&b1) + Bitsliced::<16, 512>::from(&b2);
+
+        let res_a: Box<[u8; 512]> = (&a).into();
+        let res_b: Box<[u16; 512]> = (&b).into();
+
+        for i in 0..512 {
+            assert_eq!(res_a[i], a1[i].wrapping_add(a2[i]));
+            assert_eq!(res_b[i], b1[i].wrapping_add(b2[i]));
+        }
+    }
+
+    /*
+     * Tests the signed comparisons of two i8x512s. Results are compared with cpu's
+     * comparisons of i8s.
+     */
+    #[test]
+    fn simd_lt_gt_i8x512() {
+        let mut state = SEED;
+        let a1 = init_array_i8(&mut state);
+        let a2 = init_array_i8(&mut state);
+
+        let a1_ssimd = i8x512::from(&a1);
+        let a2_ssimd = i8x512::from(&a2);
+
+        let lt = a1_ssimd.simd_lt(&a2_ssimd).to_bitmask();
+        let gt = a1_ssimd.simd_gt(&a2_ssimd).to_bitmask();
+
+        for i in 0..512 {
+            assert_eq!(lt[i / 64] >> (i % 64) & 1 == 1, a1[i] < a2[i]);
+            assert_eq!(gt[i / 64] >> (i % 64) & 1 == 1, a1[i] > a2[i]);
+        }
+    }
+
+    /*
+     * Tests the signed minimum and maximum of two i8x512s. Results are compared with cpu's
+     * i8::min and i8::max.
+     */
+    #[test]
+    fn simd_min_max_i8x512() {
+        let mut state = SEED;
+        let a1 = init_array_i8(&mut state);
+        let a2 = init_array_i8(&mut state);
+
+        let a1_ssimd = i8x512::from(&a1);
+        let a2_ssimd = i8x512::from(&a2);
+
+        let min: Box<[i8; 512]> = (&a1_ssimd.simd_min(&a2_ssimd)).into();
+        let max: Box<[i8; 512]> = (&a1_ssimd.simd_max(&a2_ssimd)).into();
+
+        for i in 0..512 {
+            assert_eq!(min[i], a1[i].min(a2[i]));
+            assert_eq!(max[i], a1[i].max(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the signed saturating addition of two i8x512s, including its boundaries. Results
+     * are compared with cpu's i8::saturating_add.
+     */
+    #[test]
+    fn saturating_add_i8x512() {
+        let mut state = SEED;
+        let mut a1 = init_array_i8(&mut state);
+        let mut a2 = init_array_i8(&mut state);
+        a1[..4].copy_from_slice(&[i8::MAX, i8::MIN, i8::MAX, -1]);
+        a2[..4].copy_from_slice(&[1, -1, 0, i8::MIN]);
+
+        let res: Box<[i8; 512]> = (&i8x512::from(&a1).saturating_add(&i8x512::from(&a2))).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].saturating_add(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the signed saturating subtraction of two i8x512s, including its boundaries.
+     * Results are compared with cpu's i8::saturating_sub.
+     */
+    #[test]
+    fn saturating_sub_i8x512() {
+        let mut state = SEED;
+        let mut a1 = init_array_i8(&mut state);
+        let mut a2 = init_array_i8(&mut state);
+        a1[..4].copy_from_slice(&[i8::MIN, i8::MAX, 0, -1]);
+        a2[..4].copy_from_slice(&[1, -1, i8::MIN, i8::MAX]);
+
+        let res: Box<[i8; 512]> = (&i8x512::from(&a1).saturating_sub(&i8x512::from(&a2))).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].saturating_sub(a2[i]));
+        }
+    }
+
+    /*
+     * Tests the widening of a u8x512 to a u16x512, and that the sum of two widened u8x512s
+     * doesn't wrap around where the sum of the u8x512s does. Results are compared with cpu's
+     * conversions and additions.
+     */
+    #[test]
+    fn widen() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        let res: Box<[u16; 512]> = (&a1_ssimd.widen()).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i] as u16);
+        }
+
+        let res: Box<[u16; 512]> = (&(a1_ssimd.widen() + a2_ssimd.widen())).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i] as u16 + a2[i] as u16);
+        }
+    }
+
+    /*
+     * Tests the saturating narrowing of a u16x512 to a u8x512, on lanes around the saturation
+     * boundary and on random lanes below 256, for which it must give back the widened u8s.
+     * Results are compared with cpu's conversions.
+     */
+    #[test]
+    fn narrow_saturating() {
+        let mut state = SEED;
+        let mut a = init_array_u16(&mut state);
+        a[..6].copy_from_slice(&[0, 254, 255, 256, 257, u16::MAX]);
+
+        let res: Box<[u8; 512]> = (&u16x512::from(&a).narrow_saturating()).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a[i].min(255) as u8);
+        }
+
+        let a = init_array(&mut state);
+
+        let res: Box<[u8; 512]> = (&u8x512::from(&a).widen().narrow_saturating()).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a[i]);
+        }
+    }
+
+    /*
+     * Tests the bitwise operations of the portable-simd backend, that wrap the operators of
+     * core::simd. Results are compared with cpu's operations on the u64s.
+     */
+    #[cfg(feature = "portable-simd")]
+    #[test]
+    fn portable_simd_ops() {
+        let mut state = SEED;
+        let mut a = [0; 8];
+        let mut b = [0; 8];
+
+        for i in 0..8 {
+            a[i] = (xorshift32(&mut state) as u64) << 32 | xorshift32(&mut state) as u64;
+            b[i] = (xorshift32(&mut state) as u64) << 32 | xorshift32(&mut state) as u64;
+        }
+
+        let (x, y) = (u64x8::from_array(a), u64x8::from_array(b));
+        let (xor, and, or) = unsafe { (simd_xor(x, y), simd_and(x, y), simd_or(x, y)) };
+
+        for i in 0..8 {
+            assert_eq!(xor.to_array()[i], a[i] ^ b[i]);
+            assert_eq!(and.to_array()[i], a[i] & b[i]);
+            assert_eq!(or.to_array()[i], a[i] | b[i]);
+        }
+    }
+
+    /*
+     * Tests the correctness of the runtime-dispatched addition of two u8x512s, and of its
+     * 256-bit path alone, which is not taken on cpus with AVX-512. Results are compared with
+     * cpu's additions.
+     */
+    #[test]
+    fn add_runtime() {
+        let mut state = SEED;
+        let a1 = init_array(&mut state);
+        let a2 = init_array(&mut state);
+
+        let a1_ssimd = u8x512::from(&a1);
+        let a2_ssimd = u8x512::from(&a2);
+
+        let res: Box<[u8; 512]> = (&a1_ssimd.add_runtime(&a2_ssimd)).into();
+        let res_tiled: Box<[u8; 512]> = (&a1_ssimd.add_tiled(&a2_ssimd)).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+            assert_eq!(res_tiled[i], a1[i].wrapping_add(a2[i]));
+        }
+    }
+
+    /*
+     * Tests that the addition compiled with AVX-512 enabled gives the same result as the Add
+     * implementation. Does nothing on cpus without AVX-512, where it can't be called.
+     */
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn add_avx512() {
+        if !is_x86_feature_detected!("avx512f") {
+            return;
+        }
+
+        let mut state = SEED;
+        let a1 = u8x512::from(&init_array(&mut state));
+        let a2 = u8x512::from(&init_array(&mut state));
+
+        assert_eq!(unsafe { a1.add_avx512(&a2) }, a1 + a2);
+    }
+
+    /*
+     * Tests that the default u8x512 has all of its lanes set to 0, like u8x512::ZERO.
+     */
+    #[test]
+    fn default() {
+        let res: Box<[u8; 512]> = (&u8x512::default()).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], 0);
+        }
+
+        assert_eq!(u8x512::default(), u8x512::ZERO);
+    }
+
+    /*
+     * Tests that two equal u8x512s, built differently, hash equally, and that a HashSet of
+     * u8x512s tells them apart from a different one.
+     */
+    #[test]
+    fn hash() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+        let b = init_array(&mut state);
+
+        let a1_ssimd = u8x512::from(&a);
+        let a2_ssimd = u8x512::from_fn(|i| a[i]);
+        let b_ssimd = u8x512::from(&b);
+
+        let hash = |x: &u8x512| {
+            let mut hasher = DefaultHasher::new();
+            x.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        assert_eq!(hash(&a1_ssimd), hash(&a2_ssimd));
+
+        let set: HashSet<u8x512> = [a1_ssimd, a2_ssimd, b_ssimd].iter().cloned().collect();
+
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&a2_ssimd));
+        assert!(set.contains(&b_ssimd));
+    }
+
+    /*
+     * Tests that a u8x512 is serialized as its 512 lanes, and that deserializing it gives it
+     * back. Sequences of the wrong length must fail to deserialize.
+     */
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+        let a_ssimd = u8x512::from(&a);
+
+        let json = serde_json::to_string(&a_ssimd).unwrap();
+
+        assert_eq!(serde_json::from_str::<Vec<u8>>(&json).unwrap(), a.to_vec());
+        assert_eq!(serde_json::from_str::<u8x512>(&json).unwrap(), a_ssimd);
+
+        let short = serde_json::to_string(&a[..511]).unwrap();
+        let long = serde_json::to_string(&[0; 513][..]).unwrap();
+
+        assert!(serde_json::from_str::<u8x512>(&short).is_err());
+        assert!(serde_json::from_str::<u8x512>(&long).is_err());
+    }
+
+    /*
+     * Tests casting a slice of u8x512s to a slice of their raw bytes and back. The bytes are
+     * the ones of the rows, so those of u8x512::ONES are all set whatever the layout.
+     */
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck_cast_slice() {
+        let mut state = SEED;
+        let vectors = vec![
+            u8x512::from(&init_array(&mut state)),
+            u8x512::ONES,
+            u8x512::from(&init_array(&mut state)),
+        ];
+
+        let bytes: &[u8] = bytemuck::cast_slice(&vectors);
+
+        assert_eq!(bytes.len(), 3 * 512);
+        assert!(bytes[512..1024].iter().all(|&byte| byte == 0xFF));
+
+        let back: &[u8x512] = bytemuck::cast_slice(bytes);
+
+        assert_eq!(back, &vectors[..]);
+    }
+
+    /*
+     * Tests the Display, LowerHex and UpperHex formattings of a u8x512 whose lane i is i
+     * modulo 256.
+     */
+    #[test]
+    fn display_hex() {
+        let a_ssimd = u8x512::from_fn(|i| i as u8);
+
+        let display = format!("{}", a_ssimd);
+        let lower = format!("{:x}", a_ssimd);
+        let upper = format!("{:X}", a_ssimd);
+
+        assert!(display.starts_with("0, 1, 2, 3, "));
+        assert!(display.ends_with(", 253, 254, 255"));
+        assert_eq!(display.split(", ").count(), 512);
+
+        assert_eq!(lower.len(), 1024);
+        assert!(lower.starts_with("000102030405060708090a0b0c0d0e0f10"));
+        assert!(lower.ends_with("f9fafbfcfdfeff"));
+        assert_eq!(upper, lower.to_uppercase());
+    }
+
+    /*
+     * Tests summing a few u8x512s, by value and by reference. Results are compared with cpu's
+     * lane by lane wrapping additions.
+     */
+    #[test]
+    fn sum() {
+        let mut state = SEED;
+        let arrays: Vec<[u8; 512]> = (0..5).map(|_| init_array(&mut state)).collect();
+        let vectors: Vec<u8x512> = arrays.iter().map(u8x512::from).collect();
+
+        let res_ref: Box<[u8; 512]> = (&vectors.iter().sum::<u8x512>()).into();
+        let res: Box<[u8; 512]> = (&vectors.into_iter().sum::<u8x512>()).into();
+
+        for i in 0..512 {
+            let expected = arrays.iter().fold(0u8, |acc, a| acc.wrapping_add(a[i]));
+            assert_eq!(res[i], expected);
+            assert_eq!(res_ref[i], expected);
+        }
+
+        assert_eq!(Vec::<u8x512>::new().into_iter().sum::<u8x512>(), u8x512::ZERO);
+    }
+
+    /*
+     * Tests iterating over a u8x512 and a reference to it with for loops, which must yield the
+     * lanes in order.
+     */
+    #[test]
+    fn into_iter() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+        let a_ssimd = u8x512::from(&a);
+
+        let mut i = 0;
+
+        for lane in &a_ssimd {
+            assert_eq!(lane, a[i]);
+            i += 1;
+        }
+
+        assert_eq!(i, 512);
+
+        let mut i = 0;
+
+        for lane in a_ssimd {
+            assert_eq!(lane, a[i]);
+            i += 1;
+        }
+
+        assert_eq!(i, 512);
+    }
+
+    /*
+     * Tests that converting a u8 to a u8x512 gives 512 copies of it.
+     */
+    #[test]
+    fn from_u8() {
+        let res: Box<[u8; 512]> = (&u8x512::from(0x5A)).into();
+
+        for i in 0..512 {
+            assert_eq!(res[i], 0x5A);
+        }
+
+        let a_ssimd: u8x512 = 7u8.into();
+        assert_eq!(a_ssimd, u8x512::splat(7));
+    }
+
+    /*
+     * Tests that the conversions from arrays, done with bit-matrix transpositions, give exactly
+     * the same rows as the reference horizontalization, for every shape.
+     */
+    #[test]
+    fn from_matches_reference() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+        let b = init_array_u16(&mut state);
+        let c = init_array_u32(&mut state);
+        let mut d = [0; 256];
+        d.copy_from_slice(&init_array(&mut state)[..256]);
+
+        assert_same_rows(&u8x512::from(&a), &horizontalize_reference(&a));
+        assert_same_rows(&u16x512::from(&b), &horizontalize_reference(&b));
+        assert_same_rows(&u32x512::from(&c), &horizontalize_reference(&c));
+        assert_same_rows(&u8x256::from(&d), &horizontalize_reference(&d));
+    }
+
+    /*
+     * Tests that the conversions to arrays, done with inverse bit-matrix transpositions, give
+     * back the original arrays and the same lanes as the reference verticalization, for every
+     * shape.
+     */
+    #[test]
+    fn into_matches_reference() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+        let b = init_array_u16(&mut state);
+        let c = init_array_u32(&mut state);
+        let mut d = [0; 256];
+        d.copy_from_slice(&init_array(&mut state)[..256]);
+
+        let mut out_a = [0; 512];
+        let mut out_b = [0; 512];
+        let mut out_c = [0; 512];
+        let mut out_d = [0; 256];
+        u8x512::from(&a).write_to(&mut out_a);
+        u16x512::from(&b).write_to(&mut out_b);
+        u32x512::from(&c).write_to(&mut out_c);
+        u8x256::from(&d).write_to(&mut out_d);
+
+        assert_eq!(out_a, a);
+        assert_eq!(out_b, b);
+        assert_eq!(out_c, c);
+        assert_eq!(out_d, d);
+
+        assert_eq!(out_a, verticalize_reference(&u8x512::from(&a)));
+        assert_eq!(out_b, verticalize_reference(&u16x512::from(&b)));
+        assert_eq!(out_c, verticalize_reference(&u32x512::from(&c)));
+        assert_eq!(out_d, verticalize_reference(&u8x256::from(&d)));
+
+        let boxed: Box<[u8; 512]> = (&u8x512::from(&a)).into();
+        assert_eq!(*boxed, a);
+    }
+
+    /*
+     * Tests add_slices against wrapping additions, with lengths that aren't multiples of 512 so
+     * that the last chunk is added one u8 after the other.
+     */
+    #[test]
+    fn add_slices_lengths() {
+        let mut state = SEED;
+
+        for len in [0, 1, 511, 512, 513, 1500] {
+            let a: Vec<u8> = (0..len).map(|_| xorshift32(&mut state) as u8).collect();
+            let b: Vec<u8> = (0..len).map(|_| xorshift32(&mut state) as u8).collect();
+            let mut out = vec![0; len];
+
+            add_slices(&a, &b, &mut out);
+
+            for i in 0..len {
+                assert_eq!(out[i], a[i].wrapping_add(b[i]));
+            }
+        }
+    }
+
+    /*
+     * Tests that add_slices panics when given slices of different lengths.
+     */
+    #[test]
+    #[should_panic]
+    fn add_slices_length_mismatch() {
+        add_slices(&[0; 600], &[0; 600], &mut [0; 599]);
+    }
+
+    /*
+     * Tests par_add_slices against add_slices on a buffer of several megabytes, whose length
+     * isn't a multiple of the blocks added in parallel.
+     */
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_add_slices_matches_serial() {
+        let mut state = SEED;
+        let len = 4 * 1024 * 1024 + 1000;
+        let a: Vec<u8> = (0..len).map(|_| xorshift32(&mut state) as u8).collect();
+        let b: Vec<u8> = (0..len).map(|_| xorshift32(&mut state) as u8).collect();
+
+        let mut serial = vec![0; len];
+        let mut parallel = vec![0; len];
+        add_slices(&a, &b, &mut serial);
+        par_add_slices(&a, &b, &mut parallel);
+
+        assert_eq!(parallel, serial);
+    }
+
+    /*
+     * Tests that par_add_slices panics when given slices of different lengths.
+     */
+    #[cfg(feature = "rayon")]
+    #[test]
+    #[should_panic]
+    fn par_add_slices_length_mismatch() {
+        par_add_slices(&[0; 600], &[0; 599], &mut [0; 600]);
+    }
+
+    /*
+     * Tests that chunks_bitsliced pads the last chunk with zeros, and that collect_bitsliced
+     * gives back the original buffer, whose length isn't a multiple of 512.
+     */
+    #[test]
+    fn chunks_bitsliced_round_trip() {
+        let mut state = SEED;
+        let data: Vec<u8> = (0..1300).map(|_| xorshift32(&mut state) as u8).collect();
+
+        let chunks: Vec<u8x512> = chunks_bitsliced(&data).collect();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[2].get(1300 - 1024 - 1), data[1299]);
+        assert!((1300 - 1024..512).all(|i| chunks[2].get(i) == 0));
+
+        assert_eq!(collect_bitsliced(chunks, data.len()), data);
+        assert_eq!(chunks_bitsliced(&[]).count(), 0);
+        assert_eq!(collect_bitsliced(chunks_bitsliced(&[]), 0), Vec::<u8>::new());
+    }
+
+    /*
+     * Tests that collect_bitsliced panics when the u8x512s have fewer lanes than asked.
+     */
+    #[test]
+    #[should_panic]
+    fn collect_bitsliced_too_short() {
+        collect_bitsliced(chunks_bitsliced(&[0; 600]), 1025);
+    }
+
+    /*
+     * Multiplies two u8s in GF(2^8) modulo the AES polynomial, one bit of rhs after the other,
+     * as a reference for the tests.
+     */
+    fn gf_mul_reference(mut a: u8, mut b: u8) -> u8 {
+        let mut res = 0;
+
+        while b != 0 {
+            if b & 1 != 0 {
+                res ^= a;
+            }
+
+            a = (a << 1) ^ if a & 0x80 != 0 { 0x1B } else { 0 };
+            b >>= 1;
+        }
+
+        res
+    }
+
+    /*
+     * Carryless product of two u8s, one bit of b after the other, as a reference for the tests.
+     */
+    fn clmul_reference(a: u8, b: u8) -> u16 {
+        let mut res = 0;
+
+        for j in 0..8 {
+            if (b >> j) & 1 != 0 {
+                res ^= (a as u16) << j;
+            }
+        }
+
+        res
+    }
+
+    /*
+     * Tests the carryless product of two u8x512s on every pair of u8s, against a scalar
+     * reference.
+     */
+    #[test]
+    fn clmul() {
+        for k in 0..128 {
+            let a = u8x512::from_fn(|i| (2 * k + i / 256) as u8);
+            let b = u8x512::from_fn(|i| i as u8);
+
+            let res = <[u16; 512]>::from(&a.clmul(&b));
+
+            for (i, &x) in res.iter().enumerate() {
+                assert_eq!(x, clmul_reference(a.get(i), b.get(i)));
+            }
+        }
+    }
+
+    /*
+     * Tests the multiplication in GF(2^8) of two u8x512s on every pair of u8s, against a scalar
+     * reference.
+     */
+    #[test]
+    fn gf_mul() {
+        for k in 0..128 {
+            let a = u8x512::from_fn(|i| (2 * k + i / 256) as u8);
+            let b = u8x512::from_fn(|i| i as u8);
+
+            let res = <[u8; 512]>::from(&a.gf_mul(&b));
+
+            for (i, &x) in res.iter().enumerate() {
+                assert_eq!(x, gf_mul_reference(a.get(i), b.get(i)));
+            }
+        }
+
+        assert_eq!(u8x512::splat(0x57).gf_mul(&u8x512::splat(0x83)), u8x512::splat(0xC1));
+    }
+
+    /*
+     * Tests the multiplicative inverse in GF(2^8) of every u8, repeated to fill 512 lanes,
+     * against a table of the inverses found by trying every u8 with the scalar reference.
+     */
+    #[test]
+    fn gf_inverse() {
+        let mut table = [0; 256];
+
+        for (a, inverse) in table.iter_mut().enumerate().skip(1) {
+            *inverse = (1..256).find(|&b| gf_mul_reference(a as u8, b as u8) == 1).unwrap() as u8;
+        }
+
+        let res = <[u8; 512]>::from(&u8x512::from_fn(|i| i as u8).gf_inverse());
+
+        for i in 0..512 {
+            assert_eq!(res[i], table[i % 256]);
+        }
+    }
+
+    /*
+     * The S-box of AES, as given by its standard.
+     */
+    const AES_SBOX: [u8; 256] = [
+        0x63, 0x7C, 0x77, 0x7B, 0xF2, 0x6B, 0x6F, 0xC5,
+        0x30, 0x01, 0x67, 0x2B, 0xFE, 0xD7, 0xAB, 0x76,
+        0xCA, 0x82, 0xC9, 0x7D, 0xFA, 0x59, 0x47, 0xF0,
+        0xAD, 0xD4, 0xA2, 0xAF, 0x9C, 0xA4, 0x72, 0xC0,
+        0xB7, 0xFD, 0x93, 0x26, 0x36, 0x3F, 0xF7, 0xCC,
+        0x34, 0xA5, 0xE5, 0xF1, 0x71, 0xD8, 0x31, 0x15,
+        0x04, 0xC7, 0x23, 0xC3, 0x18, 0x96, 0x05, 0x9A,
+        0x07, 0x12, 0x80, 0xE2, 0xEB, 0x27, 0xB2, 0x75,
+        0x09, 0x83, 0x2C, 0x1A, 0x1B, 0x6E, 0x5A, 0xA0,
+        0x52, 0x3B, 0xD6, 0xB3, 0x29, 0xE3, 0x2F, 0x84,
+        0x53, 0xD1, 0x00, 0xED, 0x20, 0xFC, 0xB1, 0x5B,
+        0x6A, 0xCB, 0xBE, 0x39, 0x4A, 0x4C, 0x58, 0xCF,
+        0xD0, 0xEF, 0xAA, 0xFB, 0x43, 0x4D, 0x33, 0x85,
+        0x45, 0xF9, 0x02, 0x7F, 0x50, 0x3C, 0x9F, 0xA8,
+        0x51, 0xA3, 0x40, 0x8F, 0x92, 0x9D, 0x38, 0xF5,
+        0xBC, 0xB6, 0xDA, 0x21, 0x10, 0xFF, 0xF3, 0xD2,
+        0xCD, 0x0C, 0x13, 0xEC, 0x5F, 0x97, 0x44, 0x17,
+        0xC4, 0xA7, 0x7E, 0x3D, 0x64, 0x5D, 0x19, 0x73,
+        0x60, 0x81, 0x4F, 0xDC, 0x22, 0x2A, 0x90, 0x88,
+        0x46, 0xEE, 0xB8, 0x14, 0xDE, 0x5E, 0x0B, 0xDB,
+        0xE0, 0x32, 0x3A, 0x0A, 0x49, 0x06, 0x24, 0x5C,
+        0xC2, 0xD3, 0xAC, 0x62, 0x91, 0x95, 0xE4, 0x79,
+        0xE7, 0xC8, 0x37, 0x6D, 0x8D, 0xD5, 0x4E, 0xA9,
+        0x6C, 0x56, 0xF4, 0xEA, 0x65, 0x7A, 0xAE, 0x08,
+        0xBA, 0x78, 0x25, 0x2E, 0x1C, 0xA6, 0xB4, 0xC6,
+        0xE8, 0xDD, 0x74, 0x1F, 0x4B, 0xBD, 0x8B, 0x8A,
+        0x70, 0x3E, 0xB5, 0x66, 0x48, 0x03, 0xF6, 0x0E,
+        0x61, 0x35, 0x57, 0xB9, 0x86, 0xC1, 0x1D, 0x9E,
+        0xE1, 0xF8, 0x98, 0x11, 0x69, 0xD9, 0x8E, 0x94,
+        0x9B, 0x1E, 0x87, 0xE9, 0xCE, 0x55, 0x28, 0xDF,
+        0x8C, 0xA1, 0x89, 0x0D, 0xBF, 0xE6, 0x42, 0x68,
+        0x41, 0x99, 0x2D, 0x0F, 0xB0, 0x54, 0xBB, 0x16,
+    ];
+
+    /*
+     * Tests the S-box of AES on every u8, repeated to fill 512 lanes, against its standard table.
+     */
+    #[test]
+    fn aes_sbox() {
+        let res = <[u8; 512]>::from(&u8x512::from_fn(|i| i as u8).aes_sbox());
+
+        for i in 0..512 {
+            assert_eq!(res[i], AES_SBOX[i % 256]);
+        }
+    }
+
+    /*
+     * Tests the number of bits set in a u8x512 against the sum of the count_ones of its lanes.
+     */
+    #[test]
+    fn total_ones() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        let expected: u32 = a.iter().map(|x| x.count_ones()).sum();
+
+        assert_eq!(u8x512::from(&a).total_ones(), expected);
+        assert_eq!(u8x512::ZERO.total_ones(), 0);
+        assert_eq!(u8x512::ONES.total_ones(), 4096);
+    }
+
+    /*
+     * Tests the Hamming distance between two u8x512s against the number of differing bits
+     * counted lane by lane.
+     */
+    #[test]
+    fn hamming_distance() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+        let b = init_array(&mut state);
+
+        let expected: u32 = (0..512).map(|i| (a[i] ^ b[i]).count_ones()).sum();
+
+        let a_ssimd = u8x512::from(&a);
+        let b_ssimd = u8x512::from(&b);
+
+        assert_eq!(a_ssimd.hamming_distance(&b_ssimd), expected);
+        assert_eq!(a_ssimd.hamming_distance(&a_ssimd), 0);
+    }
+
+    /*
+     * Tests that random gives the same u8x512s from the same seed, advanced the same way, and
+     * that its rows are the successive states of the scalar xorshift64 generator.
+     */
+    #[test]
+    fn random() {
+        let words = [1, 2, 3, 4, 5, 6, 7, 0x9E3779B97F4A7C15];
+        let mut seed1 = u64x8::from_array(words);
+        let mut seed2 = u64x8::from_array(words);
+
+        let a1 = u8x512::random(&mut seed1);
+        let a2 = u8x512::random(&mut seed2);
+        assert_eq!(a1, a2);
+        assert_eq!(seed1.to_array(), seed2.to_array());
+
+        let b1 = u8x512::random(&mut seed1);
+        assert_eq!(b1, u8x512::random(&mut seed2));
+        assert!(a1 != b1);
+
+        let mut x = words[7];
+
+        for v in 0..8 {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            assert_eq!(a1.rows[v].to_array()[7], x);
+        }
+    }
+
+    /*
+     * Tests the substitution of every u8, repeated to fill 512 lanes, with the identity table,
+     * a random permutation and the S-box of AES, against scalar lookups.
+     */
+    #[test]
+    fn substitute() {
+        let mut state = SEED;
+        let a = u8x512::from_fn(|i| i as u8);
 
-        macro_rules! horizontalize {
-            ($row: tt, $range: expr) => {
-                h_mask = 1;
+        let mut identity = [0; 256];
+        for (x, entry) in identity.iter_mut().enumerate() {
+            *entry = x as u8;
+        }
 
-                for i in $range {
-                    let mut col = cols[i].clone();
+        let mut permutation = identity;
+        for x in (1..256).rev() {
+            permutation.swap(x, xorshift32(&mut state) as usize % (x + 1));
+        }
 
-                    for _ in 0..col.count_ones() {
-                        let v = col.trailing_zeros();
-                        col ^= 1 << v;
-                        rows[v as usize].$row |= h_mask;
-                    }
+        for table in [identity, permutation, AES_SBOX].iter() {
+            let res = <[u8; 512]>::from(&a.substitute(table));
 
-                    h_mask = h_mask.wrapping_shl(1);
-                }
+            for i in 0..512 {
+                assert_eq!(res[i], table[i % 256]);
             }
         }
 
-        horizontalize!(0, 0..64);
-        horizontalize!(1, 64..128);
-        horizontalize!(2, 128..192);
-        horizontalize!(3, 192..256);
-        horizontalize!(4, 256..320);
-        horizontalize!(5, 320..384);
-        horizontalize!(6, 384..448);
-        horizontalize!(7, 448..512);
-
-        u8x512 {rows}
+        assert_eq!(a.substitute(&AES_SBOX), a.aes_sbox());
     }
-}
-
-/*
- * Converts a u8x512 to a boxed array of u8s. The algorithm is basically the
- * same as the one of used by the above function.
- */
-impl Into<Box<[u8; 512]>> for &u8x512 {
-    fn into(self) -> Box<[u8; 512]> {
-        let mut cols = [0; 512];
-        let mut v_mask: u8 = 1;
 
-        for row in self.rows.iter() {
-            let mut row_cpy;
+    /*
+     * Tests the bitwise majority of three u8x512s against a majority computed bit by bit.
+     */
+    #[test]
+    fn majority() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+        let b = init_array(&mut state);
+        let c = init_array(&mut state);
 
-            macro_rules! verticalize {
-                ($row: tt, $offset: expr) => {
-                    row_cpy = row.$row.clone();
+        let res = <[u8; 512]>::from(&u8x512::majority(
+            &u8x512::from(&a),
+            &u8x512::from(&b),
+            &u8x512::from(&c),
+        ));
 
-                    for _ in 0..row_cpy.count_ones() {
-                        let h = row_cpy.trailing_zeros();
-                        row_cpy ^= 1 << h;
-                        cols[(h + $offset) as usize] |= v_mask;
-                    }
-                }
+        for i in 0..512 {
+            for bit in 0..8 {
+                let votes = ((a[i] >> bit) & 1) + ((b[i] >> bit) & 1) + ((c[i] >> bit) & 1);
+                assert_eq!((res[i] >> bit) & 1, (votes >= 2) as u8);
             }
-
-            verticalize!(0, 0);
-            verticalize!(1, 64);
-            verticalize!(2, 128);
-            verticalize!(3, 192);
-            verticalize!(4, 256);
-            verticalize!(5, 320);
-            verticalize!(6, 384);
-            verticalize!(7, 448);
-
-            v_mask = v_mask.wrapping_shl(1);
         }
-
-        Box::new(cols)
-    }
-}
-
-/*
- * Implementation of Display for u8x512, formats it like a Vec<u8>.
- */
-impl Debug for u8x512 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let numbers: Box<[u8; 512]> = self.into();
-        numbers.to_vec().fmt(f)
     }
-}
 
-/*
- * Implementation of &u8x512 + &u8x512 -> u8x512. It's an implementation of the binary long
- * addition algorithm, done 512 times in parallel. Considering a simd operation as a
- * single operation, the complexity is equal to 2+7x6+2 = 46 operations, giving a theoretical
- * speedup of 512/46 = 11.13
- */
-impl Add for &u8x512 {
-    type Output = u8x512;
+    /*
+     * Tests the sort of the lanes of random u8x512s against the sort of their source arrays.
+     */
+    #[test]
+    fn sort_lanes() {
+        let mut state = SEED;
 
-    fn add(self, rhs: &u8x512) -> u8x512 {
-        unsafe {
-            let mut res: [MaybeUninit<u64x8>; 8] = MaybeUninit::uninit().assume_init();
+        for _ in 0..4 {
+            let mut a = init_array(&mut state);
+            let res = <[u8; 512]>::from(&u8x512::from(&a).sort_lanes());
 
-            res[0] = MaybeUninit::new(simd_xor(self.rows[0], rhs.rows[0]));
-            let mut carry = simd_and(self.rows[0], rhs.rows[0]);
+            a.sort();
 
-            for i in 1..7 {
-                res[i] = MaybeUninit::new(simd_xor(simd_xor(
-                    self.rows[i],
-                    rhs.rows[i]),
-                    carry,
-                ));
+            assert_eq!(res.to_vec(), a.to_vec());
+        }
 
-                carry = simd_or(simd_or(
-                    simd_and(self.rows[i], rhs.rows[i]),
-                    simd_and(self.rows[i], carry)),
-                    simd_and(rhs.rows[i], carry),
-                );
-            }
+        assert_eq!(u8x512::splat(42).sort_lanes(), u8x512::splat(42));
+    }
 
-            res[7] = MaybeUninit::new(simd_xor(simd_xor(
-                self.rows[7],
-                rhs.rows[7]),
-                carry,
-            ));
+    /*
+     * Tests the histogram of a u8x512 against the one of its source array.
+     */
+    #[test]
+    fn histogram_counts() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
 
-            u8x512 {rows: transmute(res)}
+        let mut expected = [0; 256];
+        for &x in a.iter() {
+            expected[x as usize] += 1;
         }
-    }
-}
-
-/*
- * Implementation of u8x512 + u8x512 -> u8x512.
- */
-impl Add for u8x512 {
-    type Output = u8x512;
 
-    #[inline(always)]
-    fn add(self, rhs: u8x512) -> u8x512 {
-        &self + &rhs
+        assert_eq!(u8x512::from(&a).histogram().to_vec(), expected.to_vec());
+        assert_eq!(u8x512::splat(7).histogram()[7], 512);
     }
-}
 
-/*
- * Implementation of &mut u8x512 += &u8x512.
- */
-impl AddAssign<&u8x512> for u8x512 {
-    fn add_assign(&mut self, rhs: &u8x512) {
-        unsafe {
-            let mut tmp;
-            let mut carry = simd_and(self.rows[0], rhs.rows[0]);
-            self.rows[0] = simd_xor(self.rows[0], rhs.rows[0]);
+    /*
+     * Tests the bitwise operators of u64x8 against the ones of its u64s, and its conversions
+     * from and to arrays.
+     */
+    #[test]
+    fn u64x8_ops() {
+        let mut state = SEED;
+        let mut a_words = [0; 8];
+        let mut b_words = [0; 8];
 
-            for i in 1..7 {
-                tmp = simd_xor(simd_xor(
-                    self.rows[i],
-                    rhs.rows[i]),
-                    carry,
-                );
+        for k in 0..8 {
+            a_words[k] = ((xorshift32(&mut state) as u64) << 32) | xorshift32(&mut state) as u64;
+            b_words[k] = ((xorshift32(&mut state) as u64) << 32) | xorshift32(&mut state) as u64;
+        }
 
-                carry = simd_or(simd_or(
-                    simd_and(self.rows[i], rhs.rows[i]),
-                    simd_and(self.rows[i], carry)),
-                    simd_and(rhs.rows[i], carry),
-                );
+        let a = u64x8::from(a_words);
+        let b = u64x8::from(b_words);
 
-                self.rows[i] = tmp;
-            }
+        let xor: [u64; 8] = (a ^ b).into();
+        let and: [u64; 8] = (a & b).into();
+        let or: [u64; 8] = (a | b).into();
+        let not: [u64; 8] = (!a).into();
 
-            self.rows[7] = simd_xor(simd_xor(
-                self.rows[7],
-                rhs.rows[7]),
-                carry,
-            );
+        for k in 0..8 {
+            assert_eq!(xor[k], a_words[k] ^ b_words[k]);
+            assert_eq!(and[k], a_words[k] & b_words[k]);
+            assert_eq!(or[k], a_words[k] | b_words[k]);
+            assert_eq!(not[k], !a_words[k]);
         }
-    }
-}
 
-/*
- * Implementation of &mut u8x512 += u8x512.
- */
-impl AddAssign for u8x512 {
-    fn add_assign(&mut self, rhs: u8x512) {
-        *self += &rhs;
+        assert_eq!(<[u64; 8]>::from(a), a_words);
+        assert_eq!(u64x8::splat(0x0123456789ABCDEF).to_array(), [0x0123456789ABCDEF; 8]);
     }
-}
 
-//#################################################################################################
-//
-//                                         mod tests
-//
-//#################################################################################################
+    /*
+     * Tests that from_slice_padded pads the slices shorter than 512 u8s with zeros and
+     * truncates the longer ones, and that to_vec_trimmed gives the first lanes back.
+     */
+    #[test]
+    fn from_slice_padded() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
 
-#[cfg(test)]
-mod tests {
-    extern crate test;
-    use test::Bencher;
+        let short = u8x512::from_slice_padded(&a[..100]);
+        assert_eq!(short.to_vec_trimmed(100), a[..100].to_vec());
+        assert!((100..512).all(|i| short.get(i) == 0));
 
-    use std::mem::{MaybeUninit, transmute};
+        let long: Vec<u8> = a.iter().chain(a.iter()).cloned().collect();
+        assert_eq!(u8x512::from_slice_padded(&long), u8x512::from(&a));
 
-    use super::u8x512;
+        assert_eq!(u8x512::from_slice_padded(&[]), u8x512::ZERO);
+        assert_eq!(u8x512::from(&a).to_vec_trimmed(512), a.to_vec());
+        assert!(u8x512::from(&a).to_vec_trimmed(0).is_empty());
+    }
 
     /*
-     * The seed used to fill the arrays before the benchmarks.s
+     * Tests that to_vec_trimmed panics when asked for more than 512 lanes.
      */
-    const SEED: u32 = 123;
+    #[test]
+    #[should_panic]
+    fn to_vec_trimmed_too_long() {
+        u8x512::ZERO.to_vec_trimmed(513);
+    }
 
     /*
-     * Fills an array of 512 u8s with pseudo-random integers. Uses the xorshift32 algorithm to
-     * generate pseudo-random 32 bits integers.
+     * Tests loading and storing u8x512s at unaligned offsets of a Vec backing buffer.
      */
-    fn init_array(state: &mut u32) -> [u8; 512] {
-        fn xorshift32(state: &mut u32) -> u32 {
-            let mut x = *state;
-            x ^= x.wrapping_shl(13);
-            x ^= x.wrapping_shr(17);
-            x ^= x.wrapping_shl(5);
-            *state = x;
-            x
-        }
+    #[test]
+    fn load_store_unaligned() {
+        let mut state = SEED;
+        let a = init_array(&mut state);
 
-        let mut res = [0; 512];
+        let mut buffer = vec![0; 1024 + 3];
+        buffer[3..515].copy_from_slice(&a);
+
+        let a_ssimd = unsafe { u8x512::load_unaligned(buffer[3..].as_ptr()) };
+        assert_eq!(a_ssimd, u8x512::from(&a));
+
+        unsafe { (a_ssimd + a_ssimd).store_unaligned(buffer[515..].as_mut_ptr()) };
 
         for i in 0..512 {
-            res[i] = (xorshift32(state) & 0xFF) as u8;
+            assert_eq!(buffer[515 + i], a[i].wrapping_add(a[i]));
         }
 
-        res
+        assert_eq!(&buffer[3..515], &a[..]);
+        assert_eq!(&buffer[..3], &[0; 3]);
     }
 
     /*
-     * Tests the correctness of the addition of two u8x512s. Results are compared with
-     * cpu's additions.
+     * Tests the rotation of the positions of the lanes against the rotate_left of the source
+     * array, for rotations within a u64, across u64s and beyond 512 lanes.
      */
     #[test]
-    fn add() {
+    fn rotate_lanes_left() {
         let mut state = SEED;
-        let a1 = init_array(&mut state);
-        let a2 = init_array(&mut state);
-
-        let a1_ssimd = u8x512::from(&a1);
-        let a2_ssimd = u8x512::from(&a2);
+        let a = init_array(&mut state);
+        let a_ssimd = u8x512::from(&a);
 
-        let res_ssimd = a1_ssimd + a2_ssimd;
-        let res: Box<[u8; 512]> = (&res_ssimd).into();
+        for &n in [0, 1, 5, 63, 64, 65, 200, 511, 512, 1000].iter() {
+            let mut expected = a;
+            expected.rotate_left(n % 512);
 
-        for i in 0..512 {
-            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+            assert_eq!(a_ssimd.rotate_lanes_left(n), u8x512::from(&expected));
         }
     }
 
     /*
-     * Tests the correctness of the addition assignement of two u8x512s. Results are compared
-     * with cpu's additions.
+     * Tests the masked addition of two u8x512s against additions done only in the lanes
+     * selected by the mask, with partial, empty and full masks.
      */
     #[test]
-    fn add_assign() {
+    fn add_masked() {
         let mut state = SEED;
-        let a1 = init_array(&mut state);
-        let a2 = init_array(&mut state);
-
-        let mut a1_ssimd = u8x512::from(&a1);
-        let a2_ssimd = u8x512::from(&a2);
+        let a = init_array(&mut state);
+        let b = init_array(&mut state);
 
-        a1_ssimd += a2_ssimd;
+        let a_ssimd = u8x512::from(&a);
+        let b_ssimd = u8x512::from(&b);
 
-        let res: Box<[u8; 512]> = (&a1_ssimd).into();
+        let mask = a_ssimd.simd_lt(&b_ssimd);
+        let res = <[u8; 512]>::from(&a_ssimd.add_masked(&b_ssimd, &mask));
 
         for i in 0..512 {
-            assert_eq!(res[i], a1[i].wrapping_add(a2[i]));
+            let expected = if a[i] < b[i] { a[i].wrapping_add(b[i]) } else { a[i] };
+            assert_eq!(res[i], expected);
         }
+
+        let none = a_ssimd.simd_lt(&a_ssimd);
+        let full = a_ssimd.simd_eq(&a_ssimd);
+
+        assert_eq!(a_ssimd.add_masked(&b_ssimd, &none), a_ssimd);
+        assert_eq!(a_ssimd.add_masked(&b_ssimd, &full), a_ssimd + b_ssimd);
     }
 
     /*
@@ -344,7 +6261,8 @@ mod tests {
 
     /*
      * Benchmarks the time it takes to add two u8x512s (not including the time it
-     * takes to convert from and into an array).
+     * takes to convert from and into an array). The operands go through black_box, so that the
+     * addition can't be hoisted out of the loop.
      */
     #[bench]
     fn super_simd(b: &mut Bencher) {
@@ -352,8 +6270,162 @@ mod tests {
         let a1 = u8x512::from(&init_array(&mut state));
         let a2 = u8x512::from(&init_array(&mut state));
 
+        b.iter(|| {
+            test::black_box(a1) + test::black_box(a2)
+        })
+    }
+
+    /*
+     * Benchmarks the time it takes to add two u8x512s with the carry-lookahead adder (not
+     * including the time it takes to convert from and into an array).
+     */
+    #[bench]
+    fn super_simd_fast(b: &mut Bencher) {
+        let mut state = SEED;
+        let a1 = u8x512::from(&init_array(&mut state));
+        let a2 = u8x512::from(&init_array(&mut state));
+
+        b.iter(|| {
+            a1.add_fast(&a2)
+        })
+    }
+
+    /*
+     * Benchmarks the time it takes to add 512 u16s with simple additions.
+     */
+    #[bench]
+    fn scalar_u16(b: &mut Bencher) {
+        let mut state = SEED;
+        let a1 = init_array_u16(&mut state);
+        let a2 = init_array_u16(&mut state);
+
+        b.iter(|| unsafe {
+            let mut res: [MaybeUninit<u16>; 512] = MaybeUninit::uninit().assume_init();
+
+            for i in 0..512 {
+                res[i] = MaybeUninit::new(a1[i].wrapping_add(a2[i]));
+            }
+
+            transmute::<_, [u16; 512]>(res)
+        })
+    }
+
+    /*
+     * Benchmarks the time it takes to add two u16x512s (not including the time it
+     * takes to convert from and into an array).
+     */
+    #[bench]
+    fn super_simd_u16(b: &mut Bencher) {
+        let mut state = SEED;
+        let a1 = u16x512::from(&init_array_u16(&mut state));
+        let a2 = u16x512::from(&init_array_u16(&mut state));
+
         b.iter(|| {
             a1 + a2
         })
     }
+
+    /*
+     * Benchmarks the time it takes to add two u8x512s with add_runtime, which takes the path
+     * chosen for the cpu running the benchmark (not including the time it takes to convert
+     * from and into an array).
+     */
+    #[bench]
+    fn super_simd_runtime(b: &mut Bencher) {
+        let mut state = SEED;
+        let a1 = u8x512::from(&init_array(&mut state));
+        let a2 = u8x512::from(&init_array(&mut state));
+
+        b.iter(|| {
+            test::black_box(a1).add_runtime(&test::black_box(a2))
+        })
+    }
+
+    /*
+     * Benchmarks the time it takes to add two u8x512s with the 256-bit path of add_runtime,
+     * whatever the cpu running the benchmark (not including the time it takes to convert from
+     * and into an array).
+     */
+    #[bench]
+    fn super_simd_tiled(b: &mut Bencher) {
+        let mut state = SEED;
+        let a1 = u8x512::from(&init_array(&mut state));
+        let a2 = u8x512::from(&init_array(&mut state));
+
+        b.iter(|| {
+            test::black_box(a1).add_tiled(&test::black_box(a2))
+        })
+    }
+
+    /*
+     * Benchmarks the time it takes to convert an array of 512 u8s to a u8x512, with the
+     * bit-matrix transposition.
+     */
+    #[bench]
+    fn from_array(b: &mut Bencher) {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        b.iter(|| {
+            u8x512::from(&a)
+        })
+    }
+
+    /*
+     * Benchmarks the time it takes to convert an array of 512 u8s to a u8x512, with the
+     * reference horizontalization looping over the bits set in each lane.
+     */
+    #[bench]
+    fn from_array_reference(b: &mut Bencher) {
+        let mut state = SEED;
+        let a = init_array(&mut state);
+
+        b.iter(|| {
+            horizontalize_reference::<8, 512>(&a)
+        })
+    }
+
+    /*
+     * Benchmarks the time it takes to convert a u8x512 to an array of 512 u8s, with the inverse
+     * bit-matrix transposition.
+     */
+    #[bench]
+    fn into_array(b: &mut Bencher) {
+        let mut state = SEED;
+        let a = u8x512::from(&init_array(&mut state));
+        let mut out = [0; 512];
+
+        b.iter(|| {
+            a.write_to(&mut out);
+            out
+        })
+    }
+
+    /*
+     * Benchmarks the time it takes to convert a u8x512 to an array of 512 u8s, with the
+     * reference verticalization looping over the bits set in each row.
+     */
+    #[bench]
+    fn into_array_reference(b: &mut Bencher) {
+        let mut state = SEED;
+        let a = u8x512::from(&init_array(&mut state));
+
+        b.iter(|| {
+            verticalize_reference(&a)
+        })
+    }
+
+    /*
+     * Benchmarks the time it takes to count the occurrences of each value among the lanes of a
+     * u8x512.
+     */
+    #[bench]
+    fn histogram(b: &mut Bencher) {
+        let mut state = SEED;
+        let a = u8x512::from(&init_array(&mut state));
+
+        b.iter(|| {
+            a.histogram()
+        })
+    }
 }